@@ -0,0 +1,43 @@
+use std::{env, ffi::OsStr, fs, path::Path};
+
+const SCHEMAS_PATH: &str = "src/schemas";
+
+/// Generate the list of all schema types for checking that the prettier covers every one of them.
+fn generate_prettier_check(out_dir: &Path) {
+    let mut files = fs::read_dir(SCHEMAS_PATH)
+        .expect("The directory of schemas should exist.")
+        .map(|entry| entry.expect("The directory of schemas should be readable.").path())
+        .filter(|path| path.extension() == Some(OsStr::new("rs")) && !path.ends_with("mod.rs"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut code = String::from("fn check_prettier_coverage() {\n    fn is_pretty<T: PrettyPrint>() {}\n");
+    for file in files {
+        let content = fs::read_to_string(&file).expect("The schema should be readable.");
+        for line in content.lines() {
+            let name = match line
+                .strip_prefix("pub struct ")
+                .and_then(|s| s.strip_suffix("Reader<'r>(&'r [u8]);"))
+            {
+                Some(name) => name,
+                None => continue,
+            };
+            code += &format!(
+                "    is_pretty::<{}>();\n    is_pretty::<{}Reader<'static>>();\n",
+                name, name
+            );
+        }
+    }
+    code += "}\n";
+
+    fs::write(out_dir.join("prettier_check.rs"), code).expect("The check of prettier should be writable.");
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SCHEMAS_PATH);
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set by cargo.");
+    if env::var_os("CARGO_FEATURE_PRETTIER").is_some() {
+        generate_prettier_check(Path::new(&out_dir));
+    }
+}
@@ -0,0 +1,340 @@
+use super::{constants::RATE_BASE, error::DasTypesError, schemas::packed::*};
+use molecule::prelude::*;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum AuctionError {
+    /// The bid is placed before started_at.
+    NotStarted,
+    /// The bid is placed at or after ended_at.
+    AlreadyEnded,
+    /// The increment_rate_each_bid of the auction is lower than auction_min_increment_rate_each_bid.
+    IncrementRateTooLow,
+    /// The bid price is lower than the minimum price required by the next bid.
+    BidPriceTooLow,
+    /// The calculation of prices overflowed u64.
+    Overflow,
+}
+
+impl From<AuctionError> for DasTypesError {
+    fn from(err: AuctionError) -> Self {
+        DasTypesError::Auction(err)
+    }
+}
+
+/// What the previous bidder should receive when they are outbid.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct BidSettlement {
+    /// The bid price paid by the previous bidder, it is 0 when the seller is the previous bidder.
+    pub prev_bidder_refund: u64,
+    /// The profit of the previous bidder, calculated from the price difference and prev_bidder_profit_rate.
+    pub prev_bidder_profit: u64,
+}
+
+/// The state machine of an AccountAuctionCell.
+///
+/// The seller is treated as the first bidder, so before anyone bids the current_bid_price is 0 and the opening_price is
+/// the price to beat.
+pub struct Auction<'r> {
+    data: AccountAuctionCellDataReader<'r>,
+    config: ConfigCellSecondaryMarketReader<'r>,
+}
+
+impl<'r> Auction<'r> {
+    pub fn new(data: AccountAuctionCellDataReader<'r>, config: ConfigCellSecondaryMarketReader<'r>) -> Self {
+        Auction { data, config }
+    }
+
+    pub fn has_bid(&self) -> bool {
+        u64::from(self.data.current_bid_price()) != 0
+    }
+
+    /// The price the next bid is compared with.
+    pub fn current_price(&self) -> u64 {
+        if self.has_bid() {
+            u64::from(self.data.current_bid_price())
+        } else {
+            u64::from(self.data.opening_price())
+        }
+    }
+
+    /// The minimum price of the next bid.
+    ///
+    /// The first bid only needs to reach the opening_price, every bid after it needs to raise the current_bid_price by
+    /// increment_rate_each_bid at least, rounding up.
+    // div_ceil is too new for the toolchain of contracts.
+    #[allow(unknown_lints, clippy::manual_div_ceil)]
+    pub fn min_bid_price(&self) -> Result<u64, AuctionError> {
+        let current = self.current_price();
+        if !self.has_bid() {
+            return Ok(current);
+        }
+
+        let rate = u32::from(self.data.increment_rate_each_bid()) as u128;
+        let base = RATE_BASE as u128;
+        let min = (current as u128 * (base + rate) + base - 1) / base;
+        if min > u64::MAX as u128 {
+            return Err(AuctionError::Overflow);
+        }
+
+        Ok(min as u64)
+    }
+
+    pub fn is_ended(&self, timestamp: u64) -> bool {
+        timestamp >= u64::from(self.data.ended_at())
+    }
+
+    pub fn verify_bid(&self, price: u64, timestamp: u64) -> Result<(), AuctionError> {
+        if timestamp < u64::from(self.data.started_at()) {
+            return Err(AuctionError::NotStarted);
+        }
+        if self.is_ended(timestamp) {
+            return Err(AuctionError::AlreadyEnded);
+        }
+        if u32::from(self.data.increment_rate_each_bid()) < u32::from(self.config.auction_min_increment_rate_each_bid())
+        {
+            return Err(AuctionError::IncrementRateTooLow);
+        }
+        if price < self.min_bid_price()? {
+            return Err(AuctionError::BidPriceTooLow);
+        }
+
+        Ok(())
+    }
+
+    /// The ended_at after a bid.
+    ///
+    /// Each bid extends ended_at by auction_duration_increment_each_bid, but the auction can not be extended beyond
+    /// started_at + auction_max_extendable_duration.
+    pub fn next_ended_at(&self) -> u64 {
+        let ended_at = u64::from(self.data.ended_at());
+        let increment = u32::from(self.config.auction_duration_increment_each_bid()) as u64;
+        let limit = u64::from(self.data.started_at())
+            .saturating_add(u32::from(self.config.auction_max_extendable_duration()) as u64);
+
+        ended_at.saturating_add(increment).min(limit).max(ended_at)
+    }
+
+    /// Calculate what the previous bidder receives when a bid with the given price is accepted.
+    pub fn settle_bid(&self, price: u64) -> Result<BidSettlement, AuctionError> {
+        let diff = price
+            .checked_sub(self.current_price())
+            .ok_or(AuctionError::BidPriceTooLow)?;
+        let rate = u32::from(self.data.prev_bidder_profit_rate()) as u128;
+        let profit = diff as u128 * rate / RATE_BASE as u128;
+        if profit > u64::MAX as u128 {
+            return Err(AuctionError::Overflow);
+        }
+        let refund = u64::from(self.data.current_bid_price());
+
+        Ok(BidSettlement {
+            prev_bidder_refund: refund,
+            prev_bidder_profit: profit as u64,
+        })
+    }
+
+    /// Verify the bid and produce the AccountAuctionCellData after it.
+    pub fn bid(
+        &self,
+        bidder_lock: Script,
+        price: u64,
+        timestamp: u64,
+    ) -> Result<(AccountAuctionCellData, BidSettlement), AuctionError> {
+        self.verify_bid(price, timestamp)?;
+        let settlement = self.settle_bid(price)?;
+        let data = self
+            .data
+            .to_entity()
+            .as_builder()
+            .ended_at(Uint64::from(self.next_ended_at()))
+            .current_bidder_lock(bidder_lock)
+            .current_bid_price(Uint64::from(price))
+            .build();
+
+        Ok((data, settlement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction_data(opening_price: u64, increment_rate: u32, current_bid_price: u64) -> AccountAuctionCellData {
+        AccountAuctionCellData::new_builder()
+            .opening_price(Uint64::from(opening_price))
+            .increment_rate_each_bid(Uint32::from(increment_rate))
+            .started_at(Uint64::from(100))
+            .ended_at(Uint64::from(200))
+            .current_bid_price(Uint64::from(current_bid_price))
+            .prev_bidder_profit_rate(Uint32::from(5000))
+            .build()
+    }
+
+    fn config() -> ConfigCellSecondaryMarket {
+        ConfigCellSecondaryMarket::new_builder()
+            .auction_min_increment_rate_each_bid(Uint32::from(0))
+            .auction_duration_increment_each_bid(Uint32::from(60))
+            .auction_max_extendable_duration(Uint32::from(150))
+            .build()
+    }
+
+    #[test]
+    fn first_bid_only_needs_opening_price() {
+        let data = auction_data(1000, 1000, 0);
+        let config = config();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+
+        assert!(!auction.has_bid());
+        assert_eq!(auction.current_price(), 1000);
+        assert_eq!(auction.min_bid_price(), Ok(1000));
+        assert_eq!(auction.verify_bid(999, 150), Err(AuctionError::BidPriceTooLow));
+        assert_eq!(auction.verify_bid(1000, 150), Ok(()));
+    }
+
+    #[test]
+    fn later_bids_need_increment_rounded_up() {
+        let data = auction_data(1000, 1000, 1001);
+        let config = config();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+
+        assert!(auction.has_bid());
+        assert_eq!(auction.current_price(), 1001);
+        // 1001 * 1.1 = 1101.1
+        assert_eq!(auction.min_bid_price(), Ok(1102));
+        assert_eq!(auction.verify_bid(1101, 150), Err(AuctionError::BidPriceTooLow));
+        assert_eq!(auction.verify_bid(1102, 150), Ok(()));
+    }
+
+    #[test]
+    fn min_bid_price_with_edge_rates() {
+        let config = config();
+
+        let data = auction_data(1000, 0, 1500);
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.min_bid_price(), Ok(1500));
+
+        let data = auction_data(1000, RATE_BASE as u32, 1500);
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.min_bid_price(), Ok(3000));
+
+        let data = auction_data(1000, RATE_BASE as u32, u64::MAX);
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.min_bid_price(), Err(AuctionError::Overflow));
+    }
+
+    #[test]
+    fn bids_outside_of_auction_time() {
+        let data = auction_data(1000, 1000, 0);
+        let config = config();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+
+        assert_eq!(auction.verify_bid(1000, 99), Err(AuctionError::NotStarted));
+        assert!(!auction.is_ended(199));
+        assert!(auction.is_ended(200));
+        assert_eq!(auction.verify_bid(1000, 200), Err(AuctionError::AlreadyEnded));
+        assert_eq!(
+            auction
+                .bid(Script::default(), 1000, 300)
+                .map(|(_, settlement)| settlement),
+            Err(AuctionError::AlreadyEnded)
+        );
+    }
+
+    #[test]
+    fn errors_propagate_as_das_types_error() {
+        fn place_bid(auction: &Auction, price: u64, timestamp: u64) -> Result<u64, DasTypesError> {
+            auction.verify_bid(price, timestamp)?;
+            Ok(auction.min_bid_price()?)
+        }
+
+        let data = auction_data(1000, 1000, 0);
+        let config = config();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(place_bid(&auction, 1000, 150).unwrap(), 1000);
+        match place_bid(&auction, 1000, 200) {
+            Err(DasTypesError::Auction(AuctionError::AlreadyEnded)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn increment_rate_lower_than_config() {
+        let data = auction_data(1000, 100, 0);
+        let config = config()
+            .as_builder()
+            .auction_min_increment_rate_each_bid(Uint32::from(500))
+            .build();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+
+        assert_eq!(auction.verify_bid(1000, 150), Err(AuctionError::IncrementRateTooLow));
+    }
+
+    #[test]
+    fn settle_first_and_later_bids() {
+        let config = config();
+
+        // The seller is the previous bidder of the first bid, so nothing is refunded.
+        let data = auction_data(1000, 1000, 0);
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(
+            auction.settle_bid(1201),
+            Ok(BidSettlement {
+                prev_bidder_refund: 0,
+                prev_bidder_profit: 100,
+            })
+        );
+
+        let data = auction_data(1000, 1000, 1200);
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(
+            auction.settle_bid(1400),
+            Ok(BidSettlement {
+                prev_bidder_refund: 1200,
+                prev_bidder_profit: 100,
+            })
+        );
+        assert_eq!(auction.settle_bid(1199), Err(AuctionError::BidPriceTooLow));
+    }
+
+    #[test]
+    fn settle_with_edge_profit_rates() {
+        let config = config();
+
+        let data = auction_data(1000, 1000, 1200)
+            .as_builder()
+            .prev_bidder_profit_rate(Uint32::from(0))
+            .build();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.settle_bid(1400).map(|s| s.prev_bidder_profit), Ok(0));
+
+        let data = auction_data(1000, 1000, 1200)
+            .as_builder()
+            .prev_bidder_profit_rate(Uint32::from(RATE_BASE as u32))
+            .build();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.settle_bid(1400).map(|s| s.prev_bidder_profit), Ok(200));
+
+        let data = auction_data(0, 1000, 1)
+            .as_builder()
+            .prev_bidder_profit_rate(Uint32::from(u32::MAX))
+            .build();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        assert_eq!(auction.settle_bid(u64::MAX), Err(AuctionError::Overflow));
+    }
+
+    #[test]
+    fn bid_extends_ended_at_within_limit() {
+        let data = auction_data(1000, 1000, 0);
+        let config = config();
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+
+        let (data, _) = auction.bid(Script::default(), 1000, 150).unwrap();
+        assert_eq!(u64::from(data.ended_at()), 250);
+        assert_eq!(u64::from(data.current_bid_price()), 1000);
+
+        // started_at + auction_max_extendable_duration is 250, so it can not be extended any more.
+        let auction = Auction::new(data.as_reader(), config.as_reader());
+        let (data, settlement) = auction.bid(Script::default(), 1100, 240).unwrap();
+        assert_eq!(u64::from(data.ended_at()), 250);
+        assert_eq!(settlement.prev_bidder_refund, 1000);
+    }
+}
@@ -0,0 +1,595 @@
+use super::{
+    compatible::DecodeMode,
+    constants::{ACCOUNT_SUFFIX, HASH_BYTES},
+    error::DasTypesError,
+    hash::EntityHash,
+    schemas::packed::*,
+};
+use alloc::vec::Vec;
+use molecule::prelude::*;
+
+pub const ACCOUNT_ID_BYTES: usize = 20;
+pub const TIMESTAMP_BYTES: usize = 8;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CellDataError {
+    /// The cell data is shorter than its layout requires, the fields are (expected, actual).
+    TooShort(usize, usize),
+    /// The cell data is longer than its fixed-length layout in strict mode, the fields are (expected, actual).
+    TooLong(usize, usize),
+    /// The hash in the cell data is not the hash of the witness.
+    HashNotMatch,
+    /// The account ID in the cell data is not the one in the witness.
+    AccountIdNotMatch,
+    /// The account in the cell data is not the one in the witness.
+    AccountNotMatch,
+}
+
+impl From<CellDataError> for DasTypesError {
+    fn from(err: CellDataError) -> Self {
+        DasTypesError::CellData(err)
+    }
+}
+
+fn read_u64(slice: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&slice[..8]);
+    u64::from_le_bytes(buf)
+}
+
+fn verify_hash<T: EntityHash>(hash: HashReader, witness: &T) -> Result<(), CellDataError> {
+    if hash.raw_data() != witness.entity_hash() {
+        return Err(CellDataError::HashNotMatch);
+    }
+
+    Ok(())
+}
+
+/// The reader of the data of AccountCell.
+///
+/// The data is in a fixed binary layout rather than molecule:
+///
+/// ```text
+/// hash(32 bytes) | id(20 bytes) | next(20 bytes) | expired_at(8 bytes) | account(with .bit suffix)
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AccountCellRawDataReader<'r>(&'r [u8]);
+
+impl<'r> AccountCellRawDataReader<'r> {
+    const ID_OFFSET: usize = HASH_BYTES;
+    const NEXT_OFFSET: usize = Self::ID_OFFSET + ACCOUNT_ID_BYTES;
+    const EXPIRED_AT_OFFSET: usize = Self::NEXT_OFFSET + ACCOUNT_ID_BYTES;
+    const ACCOUNT_OFFSET: usize = Self::EXPIRED_AT_OFFSET + TIMESTAMP_BYTES;
+
+    pub const MIN_LENGTH: usize = Self::ACCOUNT_OFFSET;
+
+    pub fn from_slice(slice: &'r [u8]) -> Result<Self, CellDataError> {
+        if slice.len() < Self::MIN_LENGTH {
+            return Err(CellDataError::TooShort(Self::MIN_LENGTH, slice.len()));
+        }
+
+        Ok(AccountCellRawDataReader(slice))
+    }
+
+    pub fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+
+    pub fn hash(&self) -> HashReader<'r> {
+        HashReader::new_unchecked(&self.0[..Self::ID_OFFSET])
+    }
+
+    pub fn id(&self) -> AccountIdReader<'r> {
+        AccountIdReader::new_unchecked(&self.0[Self::ID_OFFSET..Self::NEXT_OFFSET])
+    }
+
+    /// The ID of the next account in the linked list of all accounts.
+    pub fn next(&self) -> AccountIdReader<'r> {
+        AccountIdReader::new_unchecked(&self.0[Self::NEXT_OFFSET..Self::EXPIRED_AT_OFFSET])
+    }
+
+    pub fn expired_at(&self) -> u64 {
+        read_u64(&self.0[Self::EXPIRED_AT_OFFSET..Self::ACCOUNT_OFFSET])
+    }
+
+    /// The account with .bit suffix.
+    pub fn account(&self) -> &'r [u8] {
+        &self.0[Self::ACCOUNT_OFFSET..]
+    }
+
+    /// Check if the data matches the AccountCellData in witness.
+    pub fn verify_witness(&self, witness: &AccountCellDataReader) -> Result<(), CellDataError> {
+        verify_hash(self.hash(), witness)?;
+        if self.id().raw_data() != witness.id().raw_data() {
+            return Err(CellDataError::AccountIdNotMatch);
+        }
+
+        let mut account = witness.account().as_readable();
+        account.extend_from_slice(ACCOUNT_SUFFIX.as_bytes());
+        if self.account() != account.as_slice() {
+            return Err(CellDataError::AccountNotMatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AccountCellRawDataBuilder {
+    hash: Hash,
+    id: AccountId,
+    next: AccountId,
+    expired_at: u64,
+    account: Vec<u8>,
+}
+
+impl AccountCellRawDataBuilder {
+    pub fn hash(mut self, v: Hash) -> Self {
+        self.hash = v;
+        self
+    }
+
+    pub fn id(mut self, v: AccountId) -> Self {
+        self.id = v;
+        self
+    }
+
+    pub fn next(mut self, v: AccountId) -> Self {
+        self.next = v;
+        self
+    }
+
+    pub fn expired_at(mut self, v: u64) -> Self {
+        self.expired_at = v;
+        self
+    }
+
+    /// The account with .bit suffix.
+    pub fn account(mut self, v: &[u8]) -> Self {
+        self.account = v.to_vec();
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(AccountCellRawDataReader::MIN_LENGTH + self.account.len());
+        ret.extend_from_slice(self.hash.as_slice());
+        ret.extend_from_slice(self.id.as_slice());
+        ret.extend_from_slice(self.next.as_slice());
+        ret.extend_from_slice(&self.expired_at.to_le_bytes());
+        ret.extend_from_slice(&self.account);
+        ret
+    }
+}
+
+/// Check the length of a cell data in fixed-length layout.
+///
+/// Like the compatible readers of tables, bytes appended by later layouts are only accepted in compatible mode.
+fn verify_fixed_length(slice: &[u8], length: usize, mode: DecodeMode) -> Result<(), CellDataError> {
+    if slice.len() < length {
+        return Err(CellDataError::TooShort(length, slice.len()));
+    }
+    if slice.len() > length && mode == DecodeMode::Strict {
+        return Err(CellDataError::TooLong(length, slice.len()));
+    }
+
+    Ok(())
+}
+
+/// Generate the reader and builder of a cell data in fixed-length layout.
+///
+/// The fields are listed in the order of the layout, each field is one of `Hash`, `AccountId` and `u64`, the `u64` is
+/// in little-endian.
+macro_rules! gen_fixed_cell_data {
+    ($reader:ident, $builder:ident, {$( $field:ident: $kind:ident ),+}) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $reader<'r>(&'r [u8]);
+
+        impl<'r> $reader<'r> {
+            pub const LENGTH: usize = 0 $(+ gen_fixed_cell_data!(@size $kind))+;
+
+            pub fn from_slice(slice: &'r [u8], mode: DecodeMode) -> Result<Self, CellDataError> {
+                verify_fixed_length(slice, Self::LENGTH, mode)?;
+
+                Ok($reader(slice))
+            }
+
+            pub fn as_slice(&self) -> &'r [u8] {
+                self.0
+            }
+
+            pub fn has_extra_bytes(&self) -> bool {
+                self.0.len() > Self::LENGTH
+            }
+
+            /// The bytes appended by later layouts, they are only accepted in compatible mode.
+            pub fn extra_bytes(&self) -> &'r [u8] {
+                &self.0[Self::LENGTH..]
+            }
+
+            gen_fixed_cell_data!(@getters 0; $( $field: $kind ),+);
+        }
+
+        #[derive(Debug, Default)]
+        pub struct $builder {
+            $($field: gen_fixed_cell_data!(@builder_type $kind),)+
+        }
+
+        impl $builder {
+            $(
+                pub fn $field(mut self, v: gen_fixed_cell_data!(@builder_type $kind)) -> Self {
+                    self.$field = v;
+                    self
+                }
+            )+
+
+            pub fn build(&self) -> Vec<u8> {
+                let mut ret = Vec::with_capacity($reader::LENGTH);
+                $(gen_fixed_cell_data!(@write ret, self.$field, $kind);)+
+                ret
+            }
+        }
+    };
+    (@getters $offset:expr; $field:ident: $kind:ident $(, $rest_field:ident: $rest_kind:ident )*) => {
+        gen_fixed_cell_data!(@getter $field, $kind, $offset);
+        gen_fixed_cell_data!(@getters $offset + gen_fixed_cell_data!(@size $kind); $( $rest_field: $rest_kind ),*);
+    };
+    (@getters $offset:expr;) => {};
+    (@getter $field:ident, Hash, $offset:expr) => {
+        pub fn $field(&self) -> HashReader<'r> {
+            HashReader::new_unchecked(&self.0[$offset..$offset + HASH_BYTES])
+        }
+    };
+    (@getter $field:ident, AccountId, $offset:expr) => {
+        pub fn $field(&self) -> AccountIdReader<'r> {
+            AccountIdReader::new_unchecked(&self.0[$offset..$offset + ACCOUNT_ID_BYTES])
+        }
+    };
+    (@getter $field:ident, u64, $offset:expr) => {
+        pub fn $field(&self) -> u64 {
+            read_u64(&self.0[$offset..])
+        }
+    };
+    (@size Hash) => { HASH_BYTES };
+    (@size AccountId) => { ACCOUNT_ID_BYTES };
+    (@size u64) => { 8 };
+    (@builder_type Hash) => { Hash };
+    (@builder_type AccountId) => { AccountId };
+    (@builder_type u64) => { u64 };
+    (@write $ret:ident, $value:expr, u64) => { $ret.extend_from_slice(&$value.to_le_bytes()) };
+    (@write $ret:ident, $value:expr, $kind:ident) => { $ret.extend_from_slice($value.as_slice()) };
+}
+
+/// Implement verify_witness for the readers whose data starts with the hash of their witness.
+macro_rules! impl_verify_witness {
+    ($( $reader:ident => $witness:ident ),+) => {
+        $(
+            impl<'r> $reader<'r> {
+                /// Check if the hash in the data is the hash of the witness.
+                pub fn verify_witness(&self, witness: &$witness) -> Result<(), CellDataError> {
+                    verify_hash(self.hash(), witness)
+                }
+            }
+        )+
+    };
+}
+
+// hash(32 bytes) | block_number(8 bytes) | timestamp(8 bytes)
+//
+// The hash is the hash of the account and the owner lock args, it is not a hash of any witness.
+gen_fixed_cell_data!(ApplyRegisterCellRawDataReader, ApplyRegisterCellRawDataBuilder, {
+    hash: Hash,
+    block_number: u64,
+    timestamp: u64
+});
+
+// hash(32 bytes) | id(20 bytes)
+gen_fixed_cell_data!(PreAccountCellRawDataReader, PreAccountCellRawDataBuilder, {
+    hash: Hash,
+    id: AccountId
+});
+
+// hash(32 bytes)
+gen_fixed_cell_data!(IncomeCellRawDataReader, IncomeCellRawDataBuilder, { hash: Hash });
+gen_fixed_cell_data!(ProposalCellRawDataReader, ProposalCellRawDataBuilder, { hash: Hash });
+gen_fixed_cell_data!(AccountSaleCellRawDataReader, AccountSaleCellRawDataBuilder, { hash: Hash });
+gen_fixed_cell_data!(AccountAuctionCellRawDataReader, AccountAuctionCellRawDataBuilder, { hash: Hash });
+gen_fixed_cell_data!(OfferCellRawDataReader, OfferCellRawDataBuilder, { hash: Hash });
+
+// smt_root(32 bytes) | das_profit(8 bytes) | owner_profit(8 bytes)
+gen_fixed_cell_data!(SubAccountCellRawDataReader, SubAccountCellRawDataBuilder, {
+    smt_root: Hash,
+    das_profit: u64,
+    owner_profit: u64
+});
+
+impl_verify_witness!(
+    PreAccountCellRawDataReader => PreAccountCellDataReader,
+    IncomeCellRawDataReader => IncomeCellDataReader,
+    ProposalCellRawDataReader => ProposalCellDataReader,
+    AccountSaleCellRawDataReader => AccountSaleCellDataReader,
+    AccountAuctionCellRawDataReader => AccountAuctionCellDataReader,
+    OfferCellRawDataReader => OfferCellDataReader
+);
+
+/// The reader of the data of BalanceCell, BalanceCell stores nothing in its data.
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceCellRawDataReader<'r>(&'r [u8]);
+
+impl<'r> BalanceCellRawDataReader<'r> {
+    pub const LENGTH: usize = 0;
+
+    pub fn from_slice(slice: &'r [u8], mode: DecodeMode) -> Result<Self, CellDataError> {
+        verify_fixed_length(slice, Self::LENGTH, mode)?;
+
+        Ok(BalanceCellRawDataReader(slice))
+    }
+
+    pub fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+
+    pub fn has_extra_bytes(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    /// The bytes appended by later layouts, they are only accepted in compatible mode.
+    pub fn extra_bytes(&self) -> &'r [u8] {
+        self.0
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BalanceCellRawDataBuilder;
+
+impl BalanceCellRawDataBuilder {
+    pub fn build(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// The reader of the data of ReverseRecordCell, the data is the account with .bit suffix.
+#[derive(Debug, Clone, Copy)]
+pub struct ReverseRecordCellRawDataReader<'r>(&'r [u8]);
+
+impl<'r> ReverseRecordCellRawDataReader<'r> {
+    pub const MIN_LENGTH: usize = ACCOUNT_SUFFIX.len() + 1;
+
+    pub fn from_slice(slice: &'r [u8]) -> Result<Self, CellDataError> {
+        if slice.len() < Self::MIN_LENGTH {
+            return Err(CellDataError::TooShort(Self::MIN_LENGTH, slice.len()));
+        }
+
+        Ok(ReverseRecordCellRawDataReader(slice))
+    }
+
+    pub fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+
+    pub fn account(&self) -> &'r [u8] {
+        self.0
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReverseRecordCellRawDataBuilder {
+    account: Vec<u8>,
+}
+
+impl ReverseRecordCellRawDataBuilder {
+    /// The account with .bit suffix.
+    pub fn account(mut self, v: &[u8]) -> Self {
+        self.account = v.to_vec();
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        self.account.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn account_cell_witness(id: [u8; 20], account: &[u8]) -> AccountCellData {
+        let chars = AccountChars::new_builder()
+            .push(AccountChar::new_builder().bytes(Bytes::from(account)).build())
+            .build();
+        AccountCellData::new_builder()
+            .id(AccountId::from(id))
+            .account(chars)
+            .build()
+    }
+
+    fn account_cell_data(witness: &AccountCellData) -> Vec<u8> {
+        AccountCellRawDataBuilder::default()
+            .hash(Hash::from(witness.entity_hash()))
+            .id(witness.id())
+            .next(AccountId::from([2u8; 20]))
+            .expired_at(1_700_000_000)
+            .account(b"das.bit")
+            .build()
+    }
+
+    #[test]
+    fn account_cell_data_round_trip() {
+        let witness = account_cell_witness([1u8; 20], b"das");
+        let data = account_cell_data(&witness);
+        assert_eq!(data.len(), 32 + 20 + 20 + 8 + 7);
+        assert_eq!(&data[..32], &witness.entity_hash());
+        assert_eq!(&data[72..80], &1_700_000_000u64.to_le_bytes());
+
+        let reader = AccountCellRawDataReader::from_slice(&data).unwrap();
+        assert_eq!(reader.as_slice(), &data[..]);
+        assert_eq!(reader.hash().raw_data(), &witness.entity_hash());
+        assert_eq!(reader.id().raw_data(), &[1u8; 20]);
+        assert_eq!(reader.next().raw_data(), &[2u8; 20]);
+        assert_eq!(reader.expired_at(), 1_700_000_000);
+        assert_eq!(reader.account(), b"das.bit");
+    }
+
+    #[test]
+    fn account_cell_data_too_short() {
+        let data = account_cell_data(&account_cell_witness([1u8; 20], b"das"));
+        // Truncate inside hash, id, next and expired_at, and at each boundary between them.
+        for length in [0, 31, 32, 51, 52, 71, 72, 79].iter() {
+            assert_eq!(
+                AccountCellRawDataReader::from_slice(&data[..*length]).err(),
+                Some(CellDataError::TooShort(80, *length))
+            );
+        }
+
+        // The account may be empty, but then it never matches a witness.
+        let reader = AccountCellRawDataReader::from_slice(&data[..80]).unwrap();
+        assert!(reader.account().is_empty());
+    }
+
+    #[test]
+    fn errors_propagate_as_das_types_error() {
+        fn expired_at(data: &[u8], witness: &AccountCellData) -> Result<u64, DasTypesError> {
+            let reader = AccountCellRawDataReader::from_slice(data)?;
+            reader.verify_witness(&witness.as_reader())?;
+            Ok(reader.expired_at())
+        }
+
+        let witness = account_cell_witness([1u8; 20], b"das");
+        let data = account_cell_data(&witness);
+        assert_eq!(expired_at(&data, &witness).unwrap(), 1_700_000_000);
+        match expired_at(&data[..79], &witness) {
+            Err(DasTypesError::CellData(CellDataError::TooShort(80, 79))) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn account_cell_data_verify_witness() {
+        let witness = account_cell_witness([1u8; 20], b"das");
+        let data = account_cell_data(&witness);
+        let reader = AccountCellRawDataReader::from_slice(&data).unwrap();
+        assert_eq!(reader.verify_witness(&witness.as_reader()), Ok(()));
+
+        let mut wrong_hash = data.clone();
+        wrong_hash[0] ^= 1;
+        let reader = AccountCellRawDataReader::from_slice(&wrong_hash).unwrap();
+        assert_eq!(
+            reader.verify_witness(&witness.as_reader()),
+            Err(CellDataError::HashNotMatch)
+        );
+
+        // The hash is checked first, so the other mismatches need the hash of the witness.
+        let other_id = account_cell_witness([3u8; 20], b"das");
+        let data = AccountCellRawDataBuilder::default()
+            .hash(Hash::from(other_id.entity_hash()))
+            .id(witness.id())
+            .account(b"das.bit")
+            .build();
+        let reader = AccountCellRawDataReader::from_slice(&data).unwrap();
+        assert_eq!(
+            reader.verify_witness(&other_id.as_reader()),
+            Err(CellDataError::AccountIdNotMatch)
+        );
+
+        let other_account = account_cell_witness([1u8; 20], b"dax");
+        let data = AccountCellRawDataBuilder::default()
+            .hash(Hash::from(other_account.entity_hash()))
+            .id(witness.id())
+            .account(b"das.bit")
+            .build();
+        let reader = AccountCellRawDataReader::from_slice(&data).unwrap();
+        assert_eq!(
+            reader.verify_witness(&other_account.as_reader()),
+            Err(CellDataError::AccountNotMatch)
+        );
+
+        // The account in the data has the .bit suffix.
+        let data = AccountCellRawDataBuilder::default()
+            .hash(Hash::from(witness.entity_hash()))
+            .id(witness.id())
+            .account(b"das")
+            .build();
+        let reader = AccountCellRawDataReader::from_slice(&data).unwrap();
+        assert_eq!(
+            reader.verify_witness(&witness.as_reader()),
+            Err(CellDataError::AccountNotMatch)
+        );
+    }
+
+    fn sub_account_data() -> Vec<u8> {
+        SubAccountCellRawDataBuilder::default()
+            .smt_root(Hash::from([9u8; 32]))
+            .das_profit(5)
+            .owner_profit(7)
+            .build()
+    }
+
+    #[test]
+    fn fixed_length_in_strict_mode() {
+        let data = sub_account_data();
+        let reader = SubAccountCellRawDataReader::from_slice(&data, DecodeMode::Strict).unwrap();
+        assert_eq!(reader.smt_root().as_slice(), &[9u8; 32]);
+        assert_eq!((reader.das_profit(), reader.owner_profit()), (5, 7));
+        assert!(!reader.has_extra_bytes());
+        assert!(reader.extra_bytes().is_empty());
+
+        let mut longer = data.clone();
+        longer.push(0);
+        assert_eq!(
+            SubAccountCellRawDataReader::from_slice(&longer, DecodeMode::Strict).err(),
+            Some(CellDataError::TooLong(48, 49))
+        );
+        assert_eq!(
+            SubAccountCellRawDataReader::from_slice(&data[..47], DecodeMode::Strict).err(),
+            Some(CellDataError::TooShort(48, 47))
+        );
+    }
+
+    #[test]
+    fn fixed_length_in_compatible_mode() {
+        let mut data = sub_account_data();
+        data.extend_from_slice(&[1, 2, 3]);
+        let reader = SubAccountCellRawDataReader::from_slice(&data, DecodeMode::Compatible).unwrap();
+        assert_eq!((reader.das_profit(), reader.owner_profit()), (5, 7));
+        assert!(reader.has_extra_bytes());
+        assert_eq!(reader.extra_bytes(), &[1, 2, 3]);
+
+        // Missing bytes are never accepted.
+        assert_eq!(
+            SubAccountCellRawDataReader::from_slice(&data[..10], DecodeMode::Compatible).err(),
+            Some(CellDataError::TooShort(48, 10))
+        );
+    }
+
+    #[test]
+    fn balance_cell_data_is_empty() {
+        let data = BalanceCellRawDataBuilder.build();
+        assert!(data.is_empty());
+        let reader = BalanceCellRawDataReader::from_slice(&data, DecodeMode::Strict).unwrap();
+        assert!(!reader.has_extra_bytes());
+
+        let data = vec![1u8, 2];
+        assert_eq!(
+            BalanceCellRawDataReader::from_slice(&data, DecodeMode::Strict).err(),
+            Some(CellDataError::TooLong(0, 2))
+        );
+        let reader = BalanceCellRawDataReader::from_slice(&data, DecodeMode::Compatible).unwrap();
+        assert_eq!(reader.extra_bytes(), &[1, 2]);
+    }
+
+    #[test]
+    fn verify_witness_hash() {
+        let witness = OfferCellData::default();
+        let data = OfferCellRawDataBuilder::default()
+            .hash(Hash::from(witness.entity_hash()))
+            .build();
+        let reader = OfferCellRawDataReader::from_slice(&data, DecodeMode::Strict).unwrap();
+        assert_eq!(reader.verify_witness(&witness.as_reader()), Ok(()));
+
+        let other = OfferCellData::new_builder().price(Uint64::from(1u64)).build();
+        assert_eq!(
+            reader.verify_witness(&other.as_reader()),
+            Err(CellDataError::HashNotMatch)
+        );
+    }
+}
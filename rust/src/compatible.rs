@@ -0,0 +1,370 @@
+use super::{schemas::packed::*, util::verify_offsets};
+use alloc::borrow::ToOwned;
+use molecule::{
+    error::{VerificationError, VerificationResult},
+    prelude::*,
+    NUMBER_SIZE,
+};
+
+/// How to treat tables with more fields than the current schema.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum DecodeMode {
+    /// Only the historical layouts and the current layout are accepted, just like `from_slice`.
+    Strict,
+    /// Fields appended by future layouts are accepted and ignored, just like `from_compatible_slice`.
+    Compatible,
+}
+
+impl DecodeMode {
+    fn is_compatible(&self) -> bool {
+        *self == DecodeMode::Compatible
+    }
+}
+
+/// Verify the header of a table and return its field count.
+fn verify_table(
+    name: &str,
+    slice: &[u8],
+    min_field_count: usize,
+    max_field_count: usize,
+    mode: DecodeMode,
+) -> VerificationResult<usize> {
+    let field_count = verify_offsets(name, slice)?.len() - 1;
+    if field_count < min_field_count || (!mode.is_compatible() && field_count > max_field_count) {
+        return Err(VerificationError::FieldCountNotMatch(
+            name.to_owned(),
+            max_field_count,
+            field_count,
+        ));
+    }
+
+    Ok(field_count)
+}
+
+/// Get the bytes of the field at index, the table must have been verified.
+fn table_field(slice: &[u8], field_count: usize, index: usize) -> &[u8] {
+    let start = molecule::unpack_number(&slice[NUMBER_SIZE * (index + 1)..]) as usize;
+    if index + 1 < field_count {
+        let end = molecule::unpack_number(&slice[NUMBER_SIZE * (index + 2)..]) as usize;
+        &slice[start..end]
+    } else {
+        &slice[start..]
+    }
+}
+
+/// Generate a reader for a table whose trailing fields are missing in its historical layouts.
+///
+/// The required fields exist in every layout, the optional fields are listed in the order they were appended.
+macro_rules! gen_compatible_reader {
+    ($name:ident, $latest:ident, {$( $required_index:literal: $required:ident -> $required_type:ident ),+},
+        {$( $optional_index:literal: $optional:ident -> $optional_type:ident ),+}) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name<'r> {
+            slice: &'r [u8],
+            field_count: usize,
+        }
+
+        impl<'r> $name<'r> {
+            pub const MIN_FIELD_COUNT: usize = [$( $required_index ),+].len();
+            pub const FIELD_COUNT: usize = $latest::FIELD_COUNT;
+
+            pub fn from_slice(slice: &'r [u8], mode: DecodeMode) -> VerificationResult<Self> {
+                let field_count = verify_table(
+                    $latest::NAME,
+                    slice,
+                    Self::MIN_FIELD_COUNT,
+                    Self::FIELD_COUNT,
+                    mode,
+                )?;
+                $($required_type::verify(table_field(slice, field_count, $required_index), mode.is_compatible())?;)+
+                $(
+                    if $optional_index < field_count {
+                        $optional_type::verify(table_field(slice, field_count, $optional_index), mode.is_compatible())?;
+                    }
+                )+
+
+                Ok($name { slice, field_count })
+            }
+
+            pub fn as_slice(&self) -> &'r [u8] {
+                self.slice
+            }
+
+            pub fn field_count(&self) -> usize {
+                self.field_count
+            }
+
+            /// If the table is in the current layout, it may still has extra fields in compatible mode.
+            pub fn is_latest(&self) -> bool {
+                self.field_count >= Self::FIELD_COUNT
+            }
+
+            /// Convert to the reader of the current layout, it fails when any field is missing.
+            pub fn try_into_latest(&self) -> VerificationResult<$latest<'r>> {
+                if self.is_latest() {
+                    Ok($latest::new_unchecked(self.slice))
+                } else {
+                    Err(VerificationError::FieldCountNotMatch(
+                        $latest::NAME.to_owned(),
+                        Self::FIELD_COUNT,
+                        self.field_count,
+                    ))
+                }
+            }
+
+            $(
+                pub fn $required(&self) -> $required_type<'r> {
+                    $required_type::new_unchecked(table_field(self.slice, self.field_count, $required_index))
+                }
+            )+
+
+            $(
+                pub fn $optional(&self) -> Option<$optional_type<'r>> {
+                    if $optional_index < self.field_count {
+                        Some($optional_type::new_unchecked(table_field(
+                            self.slice,
+                            self.field_count,
+                            $optional_index,
+                        )))
+                    } else {
+                        None
+                    }
+                }
+            )+
+        }
+    };
+}
+
+gen_compatible_reader!(TypeIdTableCompatibleReader, TypeIdTableReader, {
+    0: account_cell -> HashReader,
+    1: apply_register_cell -> HashReader,
+    2: balance_cell -> HashReader,
+    3: income_cell -> HashReader,
+    4: pre_account_cell -> HashReader,
+    5: proposal_cell -> HashReader
+}, {
+    6: account_sale_cell -> HashReader,
+    7: account_auction_cell -> HashReader,
+    8: offer_cell -> HashReader,
+    9: reverse_record_cell -> HashReader,
+    10: sub_account_cell -> HashReader
+});
+
+gen_compatible_reader!(DasLockOutPointTableCompatibleReader, DasLockOutPointTableReader, {
+    0: ckb_signall -> OutPointReader,
+    1: ckb_multisign -> OutPointReader,
+    2: ckb_anyone_can_pay -> OutPointReader,
+    3: eth -> OutPointReader,
+    4: tron -> OutPointReader
+}, {
+    5: ed25519 -> OutPointReader
+});
+
+/// The reader of any historical layout of ConfigCellMain.
+///
+/// Molecule only allows a table to have more fields than its schema in compatible mode, so the witnesses of
+/// ConfigCellMain created before TypeIdTable and DasLockOutPointTable grew can not be verified by ConfigCellMainReader.
+/// This reader accepts every historical layout and exposes the fields appended later as `Option`.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigCellMainCompatibleReader<'r> {
+    slice: &'r [u8],
+    field_count: usize,
+    mode: DecodeMode,
+}
+
+impl<'r> ConfigCellMainCompatibleReader<'r> {
+    pub fn from_slice(slice: &'r [u8], mode: DecodeMode) -> VerificationResult<Self> {
+        let field_count = verify_table(
+            ConfigCellMainReader::NAME,
+            slice,
+            ConfigCellMainReader::FIELD_COUNT,
+            ConfigCellMainReader::FIELD_COUNT,
+            mode,
+        )?;
+        Uint8Reader::verify(table_field(slice, field_count, 0), mode.is_compatible())?;
+        TypeIdTableCompatibleReader::from_slice(table_field(slice, field_count, 1), mode)?;
+        DasLockOutPointTableCompatibleReader::from_slice(table_field(slice, field_count, 2), mode)?;
+
+        Ok(ConfigCellMainCompatibleReader {
+            slice,
+            field_count,
+            mode,
+        })
+    }
+
+    pub fn as_slice(&self) -> &'r [u8] {
+        self.slice
+    }
+
+    pub fn status(&self) -> Uint8Reader<'r> {
+        Uint8Reader::new_unchecked(table_field(self.slice, self.field_count, 0))
+    }
+
+    pub fn type_id_table(&self) -> TypeIdTableCompatibleReader<'r> {
+        let slice = table_field(self.slice, self.field_count, 1);
+        TypeIdTableCompatibleReader {
+            slice,
+            field_count: molecule::unpack_number(&slice[NUMBER_SIZE..]) as usize / NUMBER_SIZE - 1,
+        }
+    }
+
+    pub fn das_lock_out_point_table(&self) -> DasLockOutPointTableCompatibleReader<'r> {
+        let slice = table_field(self.slice, self.field_count, 2);
+        DasLockOutPointTableCompatibleReader {
+            slice,
+            field_count: molecule::unpack_number(&slice[NUMBER_SIZE..]) as usize / NUMBER_SIZE - 1,
+        }
+    }
+
+    /// Convert to the reader of the current layout, it fails when any field of the inner tables is missing.
+    pub fn try_into_latest(&self) -> VerificationResult<ConfigCellMainReader<'r>> {
+        self.type_id_table().try_into_latest()?;
+        self.das_lock_out_point_table().try_into_latest()?;
+        if self.mode.is_compatible() {
+            ConfigCellMainReader::from_compatible_slice(self.slice)
+        } else {
+            ConfigCellMainReader::from_slice(self.slice)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{append_field, build_table};
+    use alloc::vec::Vec;
+
+    fn hash(n: u8) -> Hash {
+        Hash::from([n; 32])
+    }
+
+    fn out_point(n: u8) -> OutPoint {
+        OutPoint::new_builder().tx_hash(hash(n)).build()
+    }
+
+    // The TypeIdTable before account_sale_cell was appended.
+    fn type_id_table_v1() -> Vec<u8> {
+        let hashes: Vec<Hash> = (0..6).map(hash).collect();
+        let fields: Vec<&[u8]> = hashes.iter().map(|v| v.as_slice()).collect();
+        build_table(&fields)
+    }
+
+    // The DasLockOutPointTable before ed25519 was appended.
+    fn das_lock_out_point_table_v1() -> Vec<u8> {
+        let out_points: Vec<OutPoint> = (0..5).map(out_point).collect();
+        let fields: Vec<&[u8]> = out_points.iter().map(|v| v.as_slice()).collect();
+        build_table(&fields)
+    }
+
+    fn config_cell_main(type_id_table: &[u8], das_lock_out_point_table: &[u8]) -> Vec<u8> {
+        build_table(&[Uint8::from(1u8).as_slice(), type_id_table, das_lock_out_point_table])
+    }
+
+    const MODES: [DecodeMode; 2] = [DecodeMode::Strict, DecodeMode::Compatible];
+
+    #[test]
+    fn decode_historical_type_id_table() {
+        let data = type_id_table_v1();
+        // The generated readers reject the historical layouts even in compatible mode.
+        assert!(TypeIdTableReader::verify(&data, false).is_err());
+        assert!(TypeIdTableReader::verify(&data, true).is_err());
+
+        for mode in MODES.iter() {
+            let reader = TypeIdTableCompatibleReader::from_slice(&data, *mode).unwrap();
+            assert_eq!(reader.field_count(), 6);
+            assert!(!reader.is_latest());
+            assert!(reader.try_into_latest().is_err());
+            assert_eq!(reader.account_cell().as_slice(), hash(0).as_slice());
+            assert_eq!(reader.proposal_cell().as_slice(), hash(5).as_slice());
+            assert!(reader.account_sale_cell().is_none());
+            assert!(reader.offer_cell().is_none());
+            assert!(reader.sub_account_cell().is_none());
+        }
+    }
+
+    #[test]
+    fn decode_historical_das_lock_out_point_table() {
+        let data = das_lock_out_point_table_v1();
+        assert!(DasLockOutPointTableReader::verify(&data, false).is_err());
+
+        for mode in MODES.iter() {
+            let reader = DasLockOutPointTableCompatibleReader::from_slice(&data, *mode).unwrap();
+            assert_eq!(reader.field_count(), 5);
+            assert_eq!(reader.tron().as_slice(), out_point(4).as_slice());
+            assert!(reader.ed25519().is_none());
+        }
+    }
+
+    #[test]
+    fn decode_historical_config_cell_main() {
+        let data = config_cell_main(&type_id_table_v1(), &das_lock_out_point_table_v1());
+        assert!(ConfigCellMainReader::verify(&data, false).is_err());
+        assert!(ConfigCellMainReader::verify(&data, true).is_err());
+
+        for mode in MODES.iter() {
+            let reader = ConfigCellMainCompatibleReader::from_slice(&data, *mode).unwrap();
+            assert_eq!(u8::from(reader.status().to_entity()), 1);
+            assert_eq!(reader.type_id_table().field_count(), 6);
+            assert!(reader.type_id_table().offer_cell().is_none());
+            assert!(reader.type_id_table().sub_account_cell().is_none());
+            assert_eq!(
+                reader.das_lock_out_point_table().eth().as_slice(),
+                out_point(3).as_slice()
+            );
+            assert!(reader.das_lock_out_point_table().ed25519().is_none());
+            assert!(reader.try_into_latest().is_err());
+        }
+    }
+
+    #[test]
+    fn decode_latest_layout() {
+        let type_id_table = TypeIdTable::new_builder().sub_account_cell(hash(10)).build();
+        let das_lock_out_point_table = DasLockOutPointTable::new_builder().ed25519(out_point(5)).build();
+        let data = ConfigCellMain::new_builder()
+            .type_id_table(type_id_table)
+            .das_lock_out_point_table(das_lock_out_point_table)
+            .build();
+
+        for mode in MODES.iter() {
+            let reader = ConfigCellMainCompatibleReader::from_slice(data.as_slice(), *mode).unwrap();
+            assert!(reader.type_id_table().is_latest());
+            assert_eq!(
+                reader.type_id_table().sub_account_cell().unwrap().as_slice(),
+                hash(10).as_slice()
+            );
+            assert_eq!(
+                reader.das_lock_out_point_table().ed25519().unwrap().as_slice(),
+                out_point(5).as_slice()
+            );
+            assert_eq!(reader.try_into_latest().unwrap().as_slice(), data.as_slice());
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_appended_fields() {
+        let type_id_table = append_field(TypeIdTable::default().as_slice(), hash(11).as_slice());
+        assert!(TypeIdTableCompatibleReader::from_slice(&type_id_table, DecodeMode::Strict).is_err());
+        let reader = TypeIdTableCompatibleReader::from_slice(&type_id_table, DecodeMode::Compatible).unwrap();
+        assert_eq!(reader.field_count(), 12);
+        assert!(reader.is_latest());
+
+        let data = config_cell_main(&type_id_table, DasLockOutPointTable::default().as_slice());
+        assert!(ConfigCellMainCompatibleReader::from_slice(&data, DecodeMode::Strict).is_err());
+        let reader = ConfigCellMainCompatibleReader::from_slice(&data, DecodeMode::Compatible).unwrap();
+        assert!(reader.try_into_latest().is_ok());
+
+        let data = append_field(ConfigCellMain::default().as_slice(), &[]);
+        assert!(ConfigCellMainCompatibleReader::from_slice(&data, DecodeMode::Strict).is_err());
+        assert!(ConfigCellMainCompatibleReader::from_slice(&data, DecodeMode::Compatible).is_ok());
+    }
+
+    #[test]
+    fn missing_required_fields_are_rejected() {
+        let hashes: Vec<Hash> = (0..5).map(hash).collect();
+        let fields: Vec<&[u8]> = hashes.iter().map(|v| v.as_slice()).collect();
+        let data = build_table(&fields);
+        for mode in MODES.iter() {
+            assert!(TypeIdTableCompatibleReader::from_slice(&data, *mode).is_err());
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use super::{
+    compatible::{ConfigCellMainCompatibleReader, DecodeMode},
+    constants::{DataType, HASH_BYTES},
+    error::DasTypesError,
+    schemas::packed::*,
+    util::{blake2b_256, parse_witness},
+};
+use alloc::vec::Vec;
+use core::cell::Cell;
+use molecule::{error::VerificationResult, prelude::*};
+
+/// Decode a config in compatible mode, so the configs with fields appended by later deployments can still be loaded.
+trait CompatibleConfig<'r>: Sized {
+    fn from_compatible_config(slice: &'r [u8]) -> VerificationResult<Self>;
+}
+
+impl<'r> CompatibleConfig<'r> for ConfigCellMainCompatibleReader<'r> {
+    fn from_compatible_config(slice: &'r [u8]) -> VerificationResult<Self> {
+        ConfigCellMainCompatibleReader::from_slice(slice, DecodeMode::Compatible)
+    }
+}
+
+macro_rules! impl_compatible_config {
+    ($( $reader:ident ),+) => {
+        $(
+            impl<'r> CompatibleConfig<'r> for $reader<'r> {
+                fn from_compatible_config(slice: &'r [u8]) -> VerificationResult<Self> {
+                    $reader::from_compatible_slice(slice)
+                }
+            }
+        )+
+    };
+}
+
+impl_compatible_config!(
+    ConfigCellAccountReader,
+    ConfigCellApplyReader,
+    ConfigCellPriceReader,
+    ConfigCellProposalReader,
+    ConfigCellProfitRateReader,
+    ConfigCellIncomeReader,
+    ConfigCellReleaseReader,
+    ConfigCellSecondaryMarketReader,
+    ConfigCellReverseResolutionReader,
+    ConfigCellSubAccountReader
+);
+
+macro_rules! gen_das_config {
+    ($( $getter:ident: $data_type:ident => $reader:ident ),+) => {
+        /// All configs of DAS loaded from the witnesses of ConfigCells.
+        ///
+        /// Each config is verified with the hash in the data of its ConfigCell and decoded in compatible mode when it is
+        /// used for the first time, then its reader is cached.
+        pub struct DasConfig<'a> {
+            witnesses: Vec<(DataType, &'a [u8])>,
+            cell_data: Vec<(DataType, &'a [u8])>,
+            $($getter: Cell<Option<$reader<'a>>>,)+
+        }
+
+        impl<'a> DasConfig<'a> {
+            /// Create with the entities of configs and the data of their ConfigCells, the entities are witnesses without
+            /// the DAS header.
+            pub fn new(witnesses: Vec<(DataType, &'a [u8])>, cell_data: Vec<(DataType, &'a [u8])>) -> Self {
+                DasConfig {
+                    witnesses,
+                    cell_data,
+                    $($getter: Cell::new(None),)+
+                }
+            }
+
+            $(
+                pub fn $getter(&self) -> Result<$reader<'a>, DasTypesError> {
+                    if let Some(reader) = self.$getter.get() {
+                        return Ok(reader);
+                    }
+
+                    let entity = self.entity(DataType::$data_type)?;
+                    let reader = $reader::from_compatible_config(entity)?;
+                    self.$getter.set(Some(reader));
+
+                    Ok(reader)
+                }
+            )+
+        }
+    };
+}
+
+gen_das_config!(
+    main: ConfigCellMain => ConfigCellMainCompatibleReader,
+    account: ConfigCellAccount => ConfigCellAccountReader,
+    apply: ConfigCellApply => ConfigCellApplyReader,
+    price: ConfigCellPrice => ConfigCellPriceReader,
+    proposal: ConfigCellProposal => ConfigCellProposalReader,
+    profit_rate: ConfigCellProfitRate => ConfigCellProfitRateReader,
+    income: ConfigCellIncome => ConfigCellIncomeReader,
+    release: ConfigCellRelease => ConfigCellReleaseReader,
+    secondary_market: ConfigCellSecondaryMarket => ConfigCellSecondaryMarketReader,
+    reverse_resolution: ConfigCellReverseResolution => ConfigCellReverseResolutionReader,
+    sub_account: ConfigCellSubAccount => ConfigCellSubAccountReader
+);
+
+impl<'a> DasConfig<'a> {
+    /// Create with raw witnesses, witnesses which are not DAS witnesses or not configs are skipped.
+    pub fn from_witnesses(witnesses: &[&'a [u8]], cell_data: Vec<(DataType, &'a [u8])>) -> Self {
+        let witnesses = witnesses
+            .iter()
+            .filter_map(|witness| parse_witness(witness))
+            .filter(|(data_type, _)| data_type.is_config_cell())
+            .collect();
+
+        Self::new(witnesses, cell_data)
+    }
+
+    /// Get the entity of a config without decoding it, it is verified with the hash at the head of the ConfigCell's
+    /// data.
+    pub fn entity(&self, data_type: DataType) -> Result<&'a [u8], DasTypesError> {
+        let find = |items: &[(DataType, &'a [u8])]| {
+            items
+                .iter()
+                .find(|(item, _)| *item == data_type)
+                .map(|(_, bytes)| *bytes)
+                .ok_or(DasTypesError::ConfigMissing(data_type))
+        };
+
+        let entity = find(&self.witnesses)?;
+        let cell_data = find(&self.cell_data)?;
+        if cell_data.len() < HASH_BYTES || blake2b_256(entity)[..] != cell_data[..HASH_BYTES] {
+            return Err(DasTypesError::ConfigHashNotMatch(data_type));
+        }
+
+        Ok(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::append_field, util::build_witness};
+    use alloc::vec;
+
+    fn account_config() -> ConfigCellAccount {
+        ConfigCellAccount::new_builder().max_length(Uint32::from(42)).build()
+    }
+
+    #[test]
+    fn load_verified_config() {
+        let account = account_config();
+        let witness = build_witness(DataType::ConfigCellAccount, account.as_slice());
+        let cell_data = blake2b_256(account.as_slice());
+        let witnesses = [&witness[..], &b"not a witness"[..]];
+        let config = DasConfig::from_witnesses(&witnesses, vec![(DataType::ConfigCellAccount, &cell_data[..])]);
+
+        assert_eq!(u32::from(config.account().unwrap().max_length()), 42);
+        // The reader is cached.
+        assert_eq!(u32::from(config.account().unwrap().max_length()), 42);
+    }
+
+    #[test]
+    fn missing_witness_or_cell_data() {
+        let account = account_config();
+        let cell_data = blake2b_256(account.as_slice());
+
+        let config = DasConfig::new(vec![], vec![(DataType::ConfigCellAccount, &cell_data[..])]);
+        match config.account() {
+            Err(DasTypesError::ConfigMissing(DataType::ConfigCellAccount)) => {}
+            other => panic!("{:?}", other),
+        }
+
+        let config = DasConfig::new(vec![(DataType::ConfigCellAccount, account.as_slice())], vec![]);
+        match config.account() {
+            Err(DasTypesError::ConfigMissing(DataType::ConfigCellAccount)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn hash_not_match() {
+        let account = account_config();
+        let other = ConfigCellAccount::default();
+        let cell_data = blake2b_256(other.as_slice());
+
+        let config = DasConfig::new(
+            vec![(DataType::ConfigCellAccount, account.as_slice())],
+            vec![(DataType::ConfigCellAccount, &cell_data[..])],
+        );
+        match config.account() {
+            Err(DasTypesError::ConfigHashNotMatch(DataType::ConfigCellAccount)) => {}
+            other => panic!("{:?}", other),
+        }
+
+        let config = DasConfig::new(
+            vec![(DataType::ConfigCellAccount, account.as_slice())],
+            vec![(DataType::ConfigCellAccount, &cell_data[..HASH_BYTES - 1])],
+        );
+        match config.account() {
+            Err(DasTypesError::ConfigHashNotMatch(DataType::ConfigCellAccount)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn load_config_with_appended_fields() {
+        let account = append_field(account_config().as_slice(), &[1, 0, 0, 0]);
+        assert!(ConfigCellAccountReader::from_slice(&account).is_err());
+        let main = append_field(ConfigCellMain::default().as_slice(), &[1, 0, 0, 0]);
+        let account_hash = blake2b_256(&account);
+        let main_hash = blake2b_256(&main);
+
+        let config = DasConfig::new(
+            vec![
+                (DataType::ConfigCellAccount, &account[..]),
+                (DataType::ConfigCellMain, &main[..]),
+            ],
+            vec![
+                (DataType::ConfigCellAccount, &account_hash[..]),
+                (DataType::ConfigCellMain, &main_hash[..]),
+            ],
+        );
+        assert_eq!(u32::from(config.account().unwrap().max_length()), 42);
+        assert_eq!(u8::from(config.main().unwrap().status()), 0);
+    }
+
+    #[test]
+    fn broken_config() {
+        let broken = [0u8; 8];
+        let hash = blake2b_256(&broken);
+
+        let config = DasConfig::new(
+            vec![(DataType::ConfigCellAccount, &broken[..])],
+            vec![(DataType::ConfigCellAccount, &hash[..])],
+        );
+        match config.account() {
+            Err(DasTypesError::Verification(_)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+}
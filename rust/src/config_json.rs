@@ -0,0 +1,187 @@
+use super::{
+    constants::DataType,
+    error::DasTypesError,
+    owned::*,
+    schemas::packed::*,
+    util::{build_witness, parse_witness},
+    verify::VerifyPath,
+};
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use molecule::prelude::*;
+
+fn json_error(err: serde_json::Error) -> DasTypesError {
+    DasTypesError::Json(alloc::format!("{}", err))
+}
+
+/// Generate the conversions between the JSON documents and the entities of configs.
+///
+/// The JSON document of a config is its owned struct with named fields, unknown and missing fields are rejected.
+macro_rules! gen_config_json {
+    ($( $data_type:ident => $entity:ident, $reader:ident, $owned:ident ),+) => {
+        /// Convert the JSON document of the config to the molecule encoded entity.
+        pub fn config_from_json(data_type: DataType, json: &str) -> Result<Vec<u8>, DasTypesError> {
+            match data_type {
+                $(
+                    DataType::$data_type => {
+                        let owned: $owned = serde_json::from_str(json).map_err(json_error)?;
+                        Ok($entity::from(owned).as_slice().to_vec())
+                    }
+                )+
+                _ => Err(DasTypesError::Unregistered(data_type)),
+            }
+        }
+
+        /// Convert the molecule encoded entity of the config to a pretty JSON document for review.
+        ///
+        /// Only the current layouts are supported, because the JSON document has every field of the current schema.
+        /// The historical layouts of ConfigCellMain are rejected, read them with `ConfigCellMainCompatibleReader`.
+        pub fn config_to_json(data_type: DataType, entity: &[u8]) -> Result<String, DasTypesError> {
+            match data_type {
+                $(
+                    DataType::$data_type => {
+                        $entity::verify_path(entity, false)?;
+                        let owned = $owned::try_from($reader::new_unchecked(entity))?;
+                        serde_json::to_string_pretty(&owned).map_err(json_error)
+                    }
+                )+
+                _ => Err(DasTypesError::Unregistered(data_type)),
+            }
+        }
+    };
+}
+
+gen_config_json!(
+    ConfigCellAccount => ConfigCellAccount, ConfigCellAccountReader, ConfigCellAccountOwned,
+    ConfigCellApply => ConfigCellApply, ConfigCellApplyReader, ConfigCellApplyOwned,
+    ConfigCellIncome => ConfigCellIncome, ConfigCellIncomeReader, ConfigCellIncomeOwned,
+    ConfigCellMain => ConfigCellMain, ConfigCellMainReader, ConfigCellMainOwned,
+    ConfigCellPrice => ConfigCellPrice, ConfigCellPriceReader, ConfigCellPriceOwned,
+    ConfigCellProposal => ConfigCellProposal, ConfigCellProposalReader, ConfigCellProposalOwned,
+    ConfigCellProfitRate => ConfigCellProfitRate, ConfigCellProfitRateReader, ConfigCellProfitRateOwned,
+    ConfigCellRelease => ConfigCellRelease, ConfigCellReleaseReader, ConfigCellReleaseOwned,
+    ConfigCellSecondaryMarket => ConfigCellSecondaryMarket, ConfigCellSecondaryMarketReader, ConfigCellSecondaryMarketOwned,
+    ConfigCellReverseResolution => ConfigCellReverseResolution, ConfigCellReverseResolutionReader,
+        ConfigCellReverseResolutionOwned,
+    ConfigCellSubAccount => ConfigCellSubAccount, ConfigCellSubAccountReader, ConfigCellSubAccountOwned
+);
+
+/// Convert the JSON document of the config to the witness of the ConfigCell.
+pub fn config_witness_from_json(data_type: DataType, json: &str) -> Result<Vec<u8>, DasTypesError> {
+    config_from_json(data_type, json).map(|entity| build_witness(data_type, &entity))
+}
+
+/// Convert the witness of a ConfigCell to its DataType and a pretty JSON document for review.
+pub fn config_witness_to_json(witness: &[u8]) -> Result<(DataType, String), DasTypesError> {
+    let (data_type, entity) = parse_witness(witness).ok_or(DasTypesError::InvalidWitness)?;
+    config_to_json(data_type, entity).map(|json| (data_type, json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_config() -> ConfigCellAccount {
+        ConfigCellAccount::new_builder()
+            .max_length(Uint32::from(42))
+            .basic_capacity(Uint64::from(20_600_000_000u64))
+            .expiration_grace_period(Uint32::from(2_592_000))
+            .record_min_ttl(Uint32::from(300))
+            .common_throttle(Uint32::from(60))
+            .build()
+    }
+
+    fn main_config() -> ConfigCellMain {
+        ConfigCellMain::new_builder()
+            .status(Uint8::from(1u8))
+            .type_id_table(
+                TypeIdTable::new_builder()
+                    .account_cell(Hash::from([1u8; 32]))
+                    .sub_account_cell(Hash::from([2u8; 32]))
+                    .build(),
+            )
+            .das_lock_out_point_table(
+                DasLockOutPointTable::new_builder()
+                    .ed25519(OutPoint::new_builder().tx_hash(Hash::from([3u8; 32])).build())
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Convert the entity to JSON and back, then convert the result to JSON again.
+    fn assert_round_trip(data_type: DataType, entity: &[u8]) {
+        let json = config_to_json(data_type, entity).unwrap();
+        let bytes = config_from_json(data_type, &json).unwrap();
+        assert_eq!(bytes, entity);
+        assert_eq!(config_to_json(data_type, &bytes).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip_configs() {
+        assert_round_trip(DataType::ConfigCellAccount, account_config().as_slice());
+        assert_round_trip(DataType::ConfigCellMain, main_config().as_slice());
+        assert_round_trip(DataType::ConfigCellMain, ConfigCellMain::default().as_slice());
+    }
+
+    #[test]
+    fn round_trip_json_documents() {
+        let json = config_to_json(DataType::ConfigCellAccount, account_config().as_slice()).unwrap();
+        assert!(json.contains("\"max_length\": 42"));
+
+        let witness = config_witness_from_json(DataType::ConfigCellAccount, &json).unwrap();
+        assert_eq!(
+            parse_witness(&witness),
+            Some((DataType::ConfigCellAccount, account_config().as_slice()))
+        );
+        assert_eq!(
+            config_witness_to_json(&witness).unwrap(),
+            (DataType::ConfigCellAccount, json)
+        );
+    }
+
+    #[test]
+    fn unknown_and_missing_fields_are_rejected() {
+        let json = r#"{"apply_min_waiting_block_number": 1, "apply_max_waiting_block_number": 2}"#;
+        assert!(config_from_json(DataType::ConfigCellApply, json).is_ok());
+
+        let unknown = r#"{"apply_min_waiting_block_number": 1, "apply_max_waiting_block_number": 2, "x": 1}"#;
+        match config_from_json(DataType::ConfigCellApply, unknown) {
+            Err(DasTypesError::Json(message)) => assert!(message.contains("unknown field")),
+            other => panic!("{:?}", other),
+        }
+
+        let missing = r#"{"apply_min_waiting_block_number": 1}"#;
+        match config_from_json(DataType::ConfigCellApply, missing) {
+            Err(DasTypesError::Json(message)) => assert!(message.contains("missing field")),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn wrong_data_type() {
+        // The document of ConfigCellAccount is not a valid document of ConfigCellApply.
+        let json = config_to_json(DataType::ConfigCellAccount, account_config().as_slice()).unwrap();
+        match config_from_json(DataType::ConfigCellApply, &json) {
+            Err(DasTypesError::Json(_)) => {}
+            other => panic!("{:?}", other),
+        }
+        match config_to_json(DataType::ConfigCellApply, account_config().as_slice()) {
+            Err(DasTypesError::VerificationAt(path, _, _)) => assert!(path.starts_with("ConfigCellApply")),
+            other => panic!("{:?}", other),
+        }
+
+        // DataTypes which are not configs.
+        match config_from_json(DataType::AccountCellData, "{}") {
+            Err(DasTypesError::Unregistered(DataType::AccountCellData)) => {}
+            other => panic!("{:?}", other),
+        }
+        match config_to_json(DataType::ActionData, &[]) {
+            Err(DasTypesError::Unregistered(DataType::ActionData)) => {}
+            other => panic!("{:?}", other),
+        }
+        match config_witness_to_json(b"dax") {
+            Err(DasTypesError::InvalidWitness) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+}
@@ -1,219 +1,400 @@
-use super::schemas::packed::{Uint32, Uint32Reader};
-use core::convert::TryFrom;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u8)]
-pub enum SystemStatus {
-    Off,
-    On,
-}
+use super::{
+    error::DasTypesError,
+    schemas::packed::{Uint32, Uint32Reader, Uint8, Uint8Reader},
+};
+use alloc::borrow::ToOwned;
+use core::{convert::TryFrom, fmt, str::FromStr};
 
 pub const PRESERVED_ACCOUNT_CELL_COUNT: u8 = 20;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u32)]
-pub enum DataType {
-    ActionData = 0,
-    AccountCellData,
-    AccountSaleCellData,
-    AccountAuctionCellData,
-    ProposalCellData,
-    PreAccountCellData,
-    IncomeCellData,
-    OfferCellData,
-    SubAccount,
-    ConfigCellAccount = 100,              // args: 0x64000000
-    ConfigCellApply = 101,                // args: 0x65000000
-    ConfigCellIncome = 103,               // args: 0x67000000
-    ConfigCellMain,                       // args: 0x68000000
-    ConfigCellPrice,                      // args: 0x69000000
-    ConfigCellProposal,                   // args: 0x6a000000
-    ConfigCellProfitRate,                 // args: 0x6b000000
-    ConfigCellRecordKeyNamespace,         // args: 0x6c000000
-    ConfigCellRelease,                    // args: 0x6d000000
-    ConfigCellUnAvailableAccount,         // args: 0x6e000000
-    ConfigCellSecondaryMarket,            // args: 0x6f000000
-    ConfigCellReverseResolution,          // args: 0x70000000
-    ConfigCellSubAccount,                 // args: 0x71000000
-    ConfigCellSubAccountBetaList,         // args: 0x72000000
-    ConfigCellPreservedAccount00 = 10000, // args: 0x10270000
-    ConfigCellPreservedAccount01,
-    ConfigCellPreservedAccount02,
-    ConfigCellPreservedAccount03,
-    ConfigCellPreservedAccount04,
-    ConfigCellPreservedAccount05,
-    ConfigCellPreservedAccount06,
-    ConfigCellPreservedAccount07,
-    ConfigCellPreservedAccount08,
-    ConfigCellPreservedAccount09,
-    ConfigCellPreservedAccount10,
-    ConfigCellPreservedAccount11,
-    ConfigCellPreservedAccount12,
-    ConfigCellPreservedAccount13,
-    ConfigCellPreservedAccount14,
-    ConfigCellPreservedAccount15,
-    ConfigCellPreservedAccount16,
-    ConfigCellPreservedAccount17,
-    ConfigCellPreservedAccount18,
-    ConfigCellPreservedAccount19,     // args: 0x23270000
-    ConfigCellCharSetEmoji = 100000,  // args: 0xa0860100
-    ConfigCellCharSetDigit = 100001,  // args: 0xa1860100
-    ConfigCellCharSetEn = 100002,     // args: 0xa2860100
-    ConfigCellCharSetZhHans = 100003, // args: 0xa3860100, not available yet
-    ConfigCellCharSetZhHant = 100004, // args: 0xa4860100, not available yet
-}
+pub const ACCOUNT_SUFFIX: &str = ".bit";
 
-impl TryFrom<u32> for DataType {
-    type Error = ();
-
-    fn try_from(v: u32) -> Result<Self, Self::Error> {
-        match v {
-            x if x == DataType::ActionData as u32 => Ok(DataType::ActionData),
-            x if x == DataType::AccountCellData as u32 => Ok(DataType::AccountCellData),
-            x if x == DataType::AccountSaleCellData as u32 => Ok(DataType::AccountSaleCellData),
-            x if x == DataType::AccountAuctionCellData as u32 => Ok(DataType::AccountAuctionCellData),
-            x if x == DataType::ProposalCellData as u32 => Ok(DataType::ProposalCellData),
-            x if x == DataType::PreAccountCellData as u32 => Ok(DataType::PreAccountCellData),
-            x if x == DataType::IncomeCellData as u32 => Ok(DataType::IncomeCellData),
-            x if x == DataType::OfferCellData as u32 => Ok(DataType::OfferCellData),
-            x if x == DataType::SubAccount as u32 => Ok(DataType::SubAccount),
-            x if x == DataType::ConfigCellAccount as u32 => Ok(DataType::ConfigCellAccount),
-            x if x == DataType::ConfigCellApply as u32 => Ok(DataType::ConfigCellApply),
-            x if x == DataType::ConfigCellIncome as u32 => Ok(DataType::ConfigCellIncome),
-            x if x == DataType::ConfigCellMain as u32 => Ok(DataType::ConfigCellMain),
-            x if x == DataType::ConfigCellPrice as u32 => Ok(DataType::ConfigCellPrice),
-            x if x == DataType::ConfigCellProposal as u32 => Ok(DataType::ConfigCellProposal),
-            x if x == DataType::ConfigCellProfitRate as u32 => Ok(DataType::ConfigCellProfitRate),
-            x if x == DataType::ConfigCellRelease as u32 => Ok(DataType::ConfigCellRelease),
-            x if x == DataType::ConfigCellSecondaryMarket as u32 => Ok(DataType::ConfigCellSecondaryMarket),
-            x if x == DataType::ConfigCellReverseResolution as u32 => Ok(DataType::ConfigCellReverseResolution),
-            x if x == DataType::ConfigCellSubAccount as u32 => Ok(DataType::ConfigCellSubAccount),
-            x if x == DataType::ConfigCellSubAccountBetaList as u32 => Ok(DataType::ConfigCellSubAccountBetaList),
-            x if x == DataType::ConfigCellRecordKeyNamespace as u32 => Ok(DataType::ConfigCellRecordKeyNamespace),
-            x if x == DataType::ConfigCellUnAvailableAccount as u32 => Ok(DataType::ConfigCellUnAvailableAccount),
-            x if x == DataType::ConfigCellPreservedAccount00 as u32 => Ok(DataType::ConfigCellPreservedAccount00),
-            x if x == DataType::ConfigCellPreservedAccount01 as u32 => Ok(DataType::ConfigCellPreservedAccount01),
-            x if x == DataType::ConfigCellPreservedAccount02 as u32 => Ok(DataType::ConfigCellPreservedAccount02),
-            x if x == DataType::ConfigCellPreservedAccount03 as u32 => Ok(DataType::ConfigCellPreservedAccount03),
-            x if x == DataType::ConfigCellPreservedAccount04 as u32 => Ok(DataType::ConfigCellPreservedAccount04),
-            x if x == DataType::ConfigCellPreservedAccount05 as u32 => Ok(DataType::ConfigCellPreservedAccount05),
-            x if x == DataType::ConfigCellPreservedAccount06 as u32 => Ok(DataType::ConfigCellPreservedAccount06),
-            x if x == DataType::ConfigCellPreservedAccount07 as u32 => Ok(DataType::ConfigCellPreservedAccount07),
-            x if x == DataType::ConfigCellPreservedAccount08 as u32 => Ok(DataType::ConfigCellPreservedAccount08),
-            x if x == DataType::ConfigCellPreservedAccount09 as u32 => Ok(DataType::ConfigCellPreservedAccount09),
-            x if x == DataType::ConfigCellPreservedAccount10 as u32 => Ok(DataType::ConfigCellPreservedAccount10),
-            x if x == DataType::ConfigCellPreservedAccount11 as u32 => Ok(DataType::ConfigCellPreservedAccount11),
-            x if x == DataType::ConfigCellPreservedAccount12 as u32 => Ok(DataType::ConfigCellPreservedAccount12),
-            x if x == DataType::ConfigCellPreservedAccount13 as u32 => Ok(DataType::ConfigCellPreservedAccount13),
-            x if x == DataType::ConfigCellPreservedAccount14 as u32 => Ok(DataType::ConfigCellPreservedAccount14),
-            x if x == DataType::ConfigCellPreservedAccount15 as u32 => Ok(DataType::ConfigCellPreservedAccount15),
-            x if x == DataType::ConfigCellPreservedAccount16 as u32 => Ok(DataType::ConfigCellPreservedAccount16),
-            x if x == DataType::ConfigCellPreservedAccount17 as u32 => Ok(DataType::ConfigCellPreservedAccount17),
-            x if x == DataType::ConfigCellPreservedAccount18 as u32 => Ok(DataType::ConfigCellPreservedAccount18),
-            x if x == DataType::ConfigCellPreservedAccount19 as u32 => Ok(DataType::ConfigCellPreservedAccount19),
-            x if x == DataType::ConfigCellCharSetEmoji as u32 => Ok(DataType::ConfigCellCharSetEmoji),
-            x if x == DataType::ConfigCellCharSetDigit as u32 => Ok(DataType::ConfigCellCharSetDigit),
-            x if x == DataType::ConfigCellCharSetEn as u32 => Ok(DataType::ConfigCellCharSetEn),
-            x if x == DataType::ConfigCellCharSetZhHans as u32 => Ok(DataType::ConfigCellCharSetZhHans),
-            x if x == DataType::ConfigCellCharSetZhHant as u32 => Ok(DataType::ConfigCellCharSetZhHant),
-            _ => Err(()),
+// The denominator of all rates stored in cells and configs, 10000 means 100%.
+pub const RATE_BASE: u64 = 10000;
+
+/// Generate an enum from a table of `Variant = value => "name"`, with TryFrom of the number and its molecule types,
+/// From into the molecule type, Display, FromStr and ALL.
+macro_rules! gen_enum {
+    ($repr:ident, $uint:ident, $uint_reader:ident, $enum_name:ident {
+        $( $variant:ident = $value:literal => $name:literal ),+
+    }) => {
+        #[derive(Debug, PartialEq, Copy, Clone)]
+        #[repr($repr)]
+        pub enum $enum_name {
+            $($variant = $value,)+
         }
-    }
-}
 
-impl TryFrom<Uint32> for DataType {
-    type Error = ();
+        impl $enum_name {
+            pub const ALL: &'static [$enum_name] = &[$( $enum_name::$variant ),+];
 
-    fn try_from(v: Uint32) -> Result<Self, Self::Error> {
-        Self::try_from(u32::from(v))
-    }
-}
+            /// The name in snake_case.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($enum_name::$variant => $name,)+
+                }
+            }
+        }
 
-impl<'r> TryFrom<Uint32Reader<'r>> for DataType {
-    type Error = ();
+        impl TryFrom<$repr> for $enum_name {
+            type Error = DasTypesError;
 
-    fn try_from(v: Uint32Reader) -> Result<Self, Self::Error> {
-        Self::try_from(u32::from(v))
-    }
+            fn try_from(v: $repr) -> Result<Self, Self::Error> {
+                match v {
+                    $($value => Ok($enum_name::$variant),)+
+                    _ => Err(DasTypesError::UnknownValue(stringify!($enum_name), v as u64)),
+                }
+            }
+        }
+
+        impl TryFrom<$uint> for $enum_name {
+            type Error = DasTypesError;
+
+            fn try_from(v: $uint) -> Result<Self, Self::Error> {
+                Self::try_from($repr::from(v))
+            }
+        }
+
+        impl<'r> TryFrom<$uint_reader<'r>> for $enum_name {
+            type Error = DasTypesError;
+
+            fn try_from(v: $uint_reader) -> Result<Self, Self::Error> {
+                Self::try_from($repr::from(v))
+            }
+        }
+
+        impl From<$enum_name> for $uint {
+            fn from(v: $enum_name) -> Self {
+                $uint::from(v as $repr)
+            }
+        }
+
+        impl fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl FromStr for $enum_name {
+            type Err = DasTypesError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok($enum_name::$variant),)+
+                    _ => Err(DasTypesError::UnknownName(stringify!($enum_name), s.to_owned())),
+                }
+            }
+        }
+    };
 }
 
-// The length of CharSetType
-pub const CHAR_SET_LENGTH: usize = 5;
+gen_enum!(u8, Uint8, Uint8Reader, SystemStatus {
+    Off = 0 => "off",
+    On = 1 => "on"
+});
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u32)]
-pub enum CharSetType {
-    Emoji,
-    Digit,
-    En,
-    ZhHans,
-    ZhHant,
-    // ⚠️ DO NOT Forget to update CHAR_SET_LENGTH at the same time.
+pub enum DataTypeCategory {
+    // The ActionData in the first witness of every DAS transaction.
+    Action,
+    // The entities of cells like AccountCell, they are wrapped in the Data of witnesses.
+    CellData,
+    // The configs stored in witnesses of ConfigCells.
+    Config,
+    // The preserved accounts stored in witnesses of ConfigCells.
+    PreservedAccount,
+    // The char sets stored in witnesses of ConfigCells.
+    CharSet,
 }
 
-impl TryFrom<u32> for CharSetType {
-    type Error = ();
+/// Generate DataType and its categories from one table, every DataType should be added to the table only.
+macro_rules! gen_data_type {
+    ($( $variant:ident = $value:literal => $name:literal in $category:ident ),+) => {
+        gen_enum!(u32, Uint32, Uint32Reader, DataType { $( $variant = $value => $name ),+ });
 
-    fn try_from(v: u32) -> Result<Self, Self::Error> {
-        match v {
-            x if x == CharSetType::Emoji as u32 => Ok(CharSetType::Emoji),
-            x if x == CharSetType::Digit as u32 => Ok(CharSetType::Digit),
-            x if x == CharSetType::En as u32 => Ok(CharSetType::En),
-            x if x == CharSetType::ZhHans as u32 => Ok(CharSetType::ZhHans),
-            x if x == CharSetType::ZhHant as u32 => Ok(CharSetType::ZhHant),
-            _ => Err(()),
+        impl DataType {
+            pub fn category(&self) -> DataTypeCategory {
+                match self {
+                    $(DataType::$variant => DataTypeCategory::$category,)+
+                }
+            }
         }
-    }
+    };
 }
 
-impl TryFrom<Uint32> for CharSetType {
-    type Error = ();
+gen_data_type!(
+    ActionData = 0 => "action_data" in Action,
+    AccountCellData = 1 => "account_cell_data" in CellData,
+    AccountSaleCellData = 2 => "account_sale_cell_data" in CellData,
+    AccountAuctionCellData = 3 => "account_auction_cell_data" in CellData,
+    ProposalCellData = 4 => "proposal_cell_data" in CellData,
+    PreAccountCellData = 5 => "pre_account_cell_data" in CellData,
+    IncomeCellData = 6 => "income_cell_data" in CellData,
+    OfferCellData = 7 => "offer_cell_data" in CellData,
+    SubAccount = 8 => "sub_account" in CellData,
+    ConfigCellAccount = 100 => "config_cell_account" in Config, // args: 0x64000000
+    ConfigCellApply = 101 => "config_cell_apply" in Config,     // args: 0x65000000
+    ConfigCellIncome = 103 => "config_cell_income" in Config,   // args: 0x67000000
+    ConfigCellMain = 104 => "config_cell_main" in Config,       // args: 0x68000000
+    ConfigCellPrice = 105 => "config_cell_price" in Config,     // args: 0x69000000
+    ConfigCellProposal = 106 => "config_cell_proposal" in Config, // args: 0x6a000000
+    ConfigCellProfitRate = 107 => "config_cell_profit_rate" in Config, // args: 0x6b000000
+    ConfigCellRecordKeyNamespace = 108 => "config_cell_record_key_namespace" in Config, // args: 0x6c000000
+    ConfigCellRelease = 109 => "config_cell_release" in Config, // args: 0x6d000000
+    ConfigCellUnAvailableAccount = 110 => "config_cell_un_available_account" in Config, // args: 0x6e000000
+    ConfigCellSecondaryMarket = 111 => "config_cell_secondary_market" in Config, // args: 0x6f000000
+    ConfigCellReverseResolution = 112 => "config_cell_reverse_resolution" in Config, // args: 0x70000000
+    ConfigCellSubAccount = 113 => "config_cell_sub_account" in Config, // args: 0x71000000
+    ConfigCellSubAccountBetaList = 114 => "config_cell_sub_account_beta_list" in Config, // args: 0x72000000
+    ConfigCellPreservedAccount00 = 10000 => "config_cell_preserved_account_00" in PreservedAccount, // args: 0x10270000
+    ConfigCellPreservedAccount01 = 10001 => "config_cell_preserved_account_01" in PreservedAccount,
+    ConfigCellPreservedAccount02 = 10002 => "config_cell_preserved_account_02" in PreservedAccount,
+    ConfigCellPreservedAccount03 = 10003 => "config_cell_preserved_account_03" in PreservedAccount,
+    ConfigCellPreservedAccount04 = 10004 => "config_cell_preserved_account_04" in PreservedAccount,
+    ConfigCellPreservedAccount05 = 10005 => "config_cell_preserved_account_05" in PreservedAccount,
+    ConfigCellPreservedAccount06 = 10006 => "config_cell_preserved_account_06" in PreservedAccount,
+    ConfigCellPreservedAccount07 = 10007 => "config_cell_preserved_account_07" in PreservedAccount,
+    ConfigCellPreservedAccount08 = 10008 => "config_cell_preserved_account_08" in PreservedAccount,
+    ConfigCellPreservedAccount09 = 10009 => "config_cell_preserved_account_09" in PreservedAccount,
+    ConfigCellPreservedAccount10 = 10010 => "config_cell_preserved_account_10" in PreservedAccount,
+    ConfigCellPreservedAccount11 = 10011 => "config_cell_preserved_account_11" in PreservedAccount,
+    ConfigCellPreservedAccount12 = 10012 => "config_cell_preserved_account_12" in PreservedAccount,
+    ConfigCellPreservedAccount13 = 10013 => "config_cell_preserved_account_13" in PreservedAccount,
+    ConfigCellPreservedAccount14 = 10014 => "config_cell_preserved_account_14" in PreservedAccount,
+    ConfigCellPreservedAccount15 = 10015 => "config_cell_preserved_account_15" in PreservedAccount,
+    ConfigCellPreservedAccount16 = 10016 => "config_cell_preserved_account_16" in PreservedAccount,
+    ConfigCellPreservedAccount17 = 10017 => "config_cell_preserved_account_17" in PreservedAccount,
+    ConfigCellPreservedAccount18 = 10018 => "config_cell_preserved_account_18" in PreservedAccount,
+    ConfigCellPreservedAccount19 = 10019 => "config_cell_preserved_account_19" in PreservedAccount, // args: 0x23270000
+    ConfigCellCharSetEmoji = 100000 => "config_cell_char_set_emoji" in CharSet, // args: 0xa0860100
+    ConfigCellCharSetDigit = 100001 => "config_cell_char_set_digit" in CharSet, // args: 0xa1860100
+    ConfigCellCharSetEn = 100002 => "config_cell_char_set_en" in CharSet, // args: 0xa2860100
+    ConfigCellCharSetZhHans = 100003 => "config_cell_char_set_zh_hans" in CharSet, // args: 0xa3860100, not available yet
+    ConfigCellCharSetZhHant = 100004 => "config_cell_char_set_zh_hant" in CharSet // args: 0xa4860100, not available yet
+);
 
-    fn try_from(v: Uint32) -> Result<Self, Self::Error> {
-        Self::try_from(u32::from(v))
+impl DataType {
+    pub fn is_cell_data(&self) -> bool {
+        self.category() == DataTypeCategory::CellData
     }
-}
 
-impl<'r> TryFrom<Uint32Reader<'r>> for CharSetType {
-    type Error = ();
+    /// All DataTypes stored in witnesses of ConfigCells, including preserved accounts and char sets.
+    pub fn is_config_cell(&self) -> bool {
+        match self.category() {
+            DataTypeCategory::Config | DataTypeCategory::PreservedAccount | DataTypeCategory::CharSet => true,
+            DataTypeCategory::Action | DataTypeCategory::CellData => false,
+        }
+    }
 
-    fn try_from(v: Uint32Reader) -> Result<Self, Self::Error> {
-        Self::try_from(u32::from(v))
+    pub fn is_preserved_account(&self) -> bool {
+        self.category() == DataTypeCategory::PreservedAccount
     }
-}
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u8)]
-pub enum ProposalSliceItemType {
-    Exist,
-    Proposed,
-    New,
-}
+    pub fn is_char_set(&self) -> bool {
+        self.category() == DataTypeCategory::CharSet
+    }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u8)]
-pub enum AccountStatus {
-    Normal,
-    Selling,
-    Auction,
-}
+    /// The args of the type script of the ConfigCell, it is the DataType in little-endian, like 0x64000000 for
+    /// ConfigCellAccount.
+    pub fn config_cell_type_args(&self) -> Option<[u8; 4]> {
+        if self.is_config_cell() {
+            Some((*self as u32).to_le_bytes())
+        } else {
+            None
+        }
+    }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u8)]
-pub enum SubAccountEnableStatus {
-    Off,
-    On,
+    pub fn from_config_cell_args(args: &[u8]) -> Option<Self> {
+        if args.len() != 4 {
+            return None;
+        }
+
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(args);
+        DataType::try_from(u32::from_le_bytes(buf))
+            .ok()
+            .filter(|data_type| data_type.is_config_cell())
+    }
 }
 
+gen_enum!(u32, Uint32, Uint32Reader, CharSetType {
+    Emoji = 0 => "emoji",
+    Digit = 1 => "digit",
+    En = 2 => "en",
+    ZhHans = 3 => "zh_hans",
+    ZhHant = 4 => "zh_hant"
+});
+
+// The length of CharSetType
+pub const CHAR_SET_LENGTH: usize = CharSetType::ALL.len();
+
+gen_enum!(u8, Uint8, Uint8Reader, ProposalSliceItemType {
+    Exist = 0 => "exist",
+    Proposed = 1 => "proposed",
+    New = 2 => "new"
+});
+
+gen_enum!(u8, Uint8, Uint8Reader, AccountStatus {
+    Normal = 0 => "normal",
+    Selling = 1 => "selling",
+    Auction = 2 => "auction"
+});
+
+gen_enum!(u8, Uint8, Uint8Reader, SubAccountEnableStatus {
+    Off = 0 => "off",
+    On = 1 => "on"
+});
+
+// The kinds of cells whose type ID are listed in TypeIdTable.
 #[derive(Debug, PartialEq, Copy, Clone)]
-#[repr(u8)]
-pub enum LockRole {
-    Owner,
-    Manager,
+pub enum DasCellKind {
+    AccountCell,
+    ApplyRegisterCell,
+    BalanceCell,
+    IncomeCell,
+    PreAccountCell,
+    ProposalCell,
+    AccountSaleCell,
+    AccountAuctionCell,
+    OfferCell,
+    ReverseRecordCell,
+    SubAccountCell,
 }
 
+// The hash_type of scripts which referencing their code by type ID.
+pub const SCRIPT_HASH_TYPE_TYPE: u8 = 1;
+
+gen_enum!(u8, Uint8, Uint8Reader, LockRole {
+    Owner = 0 => "owner",
+    Manager = 1 => "manager"
+});
+
 // [100, 97, 115] equals b"das"
 pub const WITNESS_HEADER: [u8; 3] = [100, 97, 115];
 pub const WITNESS_HEADER_BYTES: usize = WITNESS_HEADER.len();
 pub const WITNESS_TYPE_BYTES: usize = 4;
 pub const WITNESS_LENGTH_BYTES: usize = 4;
+
+// The personalization of blake2b used by CKB, all hashes stored in DAS cells are calculated with it.
+pub const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
+pub const HASH_BYTES: usize = 32;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    macro_rules! assert_enum_round_trips {
+        ($( $enum_name:ident: $repr:ident, $uint:ident ),+) => {
+            $(
+                for item in $enum_name::ALL.iter() {
+                    let value = *item as $repr;
+                    assert_eq!($enum_name::try_from(value).unwrap(), *item);
+                    let uint = $uint::from(*item);
+                    assert_eq!($repr::from(uint.clone()), value);
+                    assert_eq!($enum_name::try_from(uint.clone()).unwrap(), *item);
+                    assert_eq!($enum_name::try_from(uint.as_reader()).unwrap(), *item);
+
+                    assert_eq!(item.to_string(), item.name());
+                    assert_eq!(item.name().parse::<$enum_name>().unwrap(), *item);
+                }
+            )+
+        };
+    }
+
+    #[test]
+    fn enums_round_trip() {
+        assert_enum_round_trips!(
+            SystemStatus: u8, Uint8,
+            DataType: u32, Uint32,
+            CharSetType: u32, Uint32,
+            ProposalSliceItemType: u8, Uint8,
+            AccountStatus: u8, Uint8,
+            SubAccountEnableStatus: u8, Uint8,
+            LockRole: u8, Uint8
+        );
+    }
+
+    #[test]
+    fn unknown_values_and_names() {
+        match AccountStatus::try_from(3u8) {
+            Err(DasTypesError::UnknownValue(name, value)) => assert_eq!((name, value), ("AccountStatus", 3)),
+            other => panic!("{:?}", other),
+        }
+        match DataType::try_from(Uint32::from(102)) {
+            Err(DasTypesError::UnknownValue(name, value)) => assert_eq!((name, value), ("DataType", 102)),
+            other => panic!("{:?}", other),
+        }
+        match "Auction".parse::<AccountStatus>() {
+            Err(DasTypesError::UnknownName(name, value)) => {
+                assert_eq!((name, value.as_str()), ("AccountStatus", "Auction"))
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn config_cell_type_args() {
+        assert_eq!(
+            DataType::ConfigCellAccount.config_cell_type_args(),
+            Some([0x64, 0x00, 0x00, 0x00])
+        );
+        assert_eq!(
+            DataType::ConfigCellPreservedAccount00.config_cell_type_args(),
+            Some([0x10, 0x27, 0x00, 0x00])
+        );
+        assert_eq!(
+            DataType::ConfigCellPreservedAccount19.config_cell_type_args(),
+            Some([0x23, 0x27, 0x00, 0x00])
+        );
+        assert_eq!(
+            DataType::ConfigCellCharSetEmoji.config_cell_type_args(),
+            Some([0xa0, 0x86, 0x01, 0x00])
+        );
+        assert_eq!(DataType::ActionData.config_cell_type_args(), None);
+        assert_eq!(DataType::AccountCellData.config_cell_type_args(), None);
+
+        for data_type in DataType::ALL.iter() {
+            match data_type.config_cell_type_args() {
+                Some(args) => assert_eq!(DataType::from_config_cell_args(&args), Some(*data_type)),
+                None => assert!(!data_type.is_config_cell()),
+            }
+        }
+
+        // DataTypes which are not stored in ConfigCells.
+        assert_eq!(DataType::from_config_cell_args(&[1, 0, 0, 0]), None);
+        assert_eq!(DataType::from_config_cell_args(&[0x66, 0, 0, 0]), None);
+        assert_eq!(DataType::from_config_cell_args(&[0x64, 0, 0]), None);
+        assert_eq!(DataType::from_config_cell_args(&[0x64, 0, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn data_type_categories() {
+        assert_eq!(DataType::ActionData.category(), DataTypeCategory::Action);
+        assert!(DataType::AccountCellData.is_cell_data());
+        assert!(DataType::SubAccount.is_cell_data());
+        assert!(!DataType::AccountCellData.is_config_cell());
+
+        assert!(DataType::ConfigCellMain.is_config_cell());
+        assert!(!DataType::ConfigCellMain.is_preserved_account());
+        assert!(!DataType::ConfigCellMain.is_char_set());
+
+        assert!(DataType::ConfigCellPreservedAccount07.is_config_cell());
+        assert!(DataType::ConfigCellPreservedAccount07.is_preserved_account());
+        assert!(!DataType::ConfigCellPreservedAccount07.is_char_set());
+
+        assert!(DataType::ConfigCellCharSetEn.is_config_cell());
+        assert!(DataType::ConfigCellCharSetEn.is_char_set());
+        assert!(!DataType::ConfigCellCharSetEn.is_cell_data());
+
+        let preserved_accounts = DataType::ALL.iter().filter(|v| v.is_preserved_account()).count();
+        assert_eq!(preserved_accounts, PRESERVED_ACCOUNT_CELL_COUNT as usize);
+        let char_sets = DataType::ALL.iter().filter(|v| v.is_char_set()).count();
+        assert_eq!(char_sets, CHAR_SET_LENGTH);
+    }
+
+    #[test]
+    fn char_set_length() {
+        assert_eq!(CHAR_SET_LENGTH, CharSetType::ALL.len());
+        assert_eq!(CHAR_SET_LENGTH, 5);
+    }
+}
@@ -1,8 +1,7 @@
-use super::schemas::packed::*;
-use alloc::string::{FromUtf8Error, String};
-use ckb_std::ckb_types::packed;
+use super::{error::DasTypesError, schemas::packed::*};
+use alloc::string::String;
 use core::convert::TryFrom;
-use molecule::{bytes, error::VerificationError, prelude::*};
+use molecule::{bytes, prelude::*};
 
 /// Implement convert between primitive type and molecule types
 macro_rules! impl_uint_convert {
@@ -37,6 +36,13 @@ impl_uint_convert!(u64, Uint64, Uint64Reader, 8);
 
 impl_uint_convert!(u64, Timestamp, TimestampReader, 8);
 
+/// Convert the duration since the Unix epoch to schemas::basic::Timestamp in seconds
+impl From<core::time::Duration> for Timestamp {
+    fn from(v: core::time::Duration) -> Self {
+        Timestamp::from(v.as_secs())
+    }
+}
+
 /// Convert &[u8] to schemas::basic::Bytes
 ///
 /// The difference with from_slice is that it does not require a dynvec header.
@@ -57,34 +63,24 @@ impl From<Vec<u8>> for Bytes {
     }
 }
 
-/// Convert bytes::Bytes to schemas::basic::Bytes
-impl From<bytes::Bytes> for Bytes {
-    fn from(v: bytes::Bytes) -> Self {
-        Bytes::from(v.as_ref())
-    }
-}
-
-impl From<packed::Bytes> for Bytes {
-    fn from(v: packed::Bytes) -> Self {
-        Bytes::new_unchecked(v.as_bytes().into())
-    }
-}
-
-impl Into<packed::Bytes> for Bytes {
-    fn into(self) -> packed::Bytes {
-        packed::Bytes::new_unchecked(self.as_bytes().into())
+/// Convert &str to schemas::basic::Bytes
+impl From<&str> for Bytes {
+    fn from(v: &str) -> Self {
+        Bytes::from(v.as_bytes())
     }
 }
 
-impl<'r> From<packed::BytesReader<'r>> for BytesReader<'r> {
-    fn from(v: packed::BytesReader<'r>) -> Self {
-        BytesReader::new_unchecked(v.as_slice())
+/// Convert String to schemas::basic::Bytes
+impl From<String> for Bytes {
+    fn from(v: String) -> Self {
+        Bytes::from(v.into_bytes())
     }
 }
 
-impl<'r> Into<packed::BytesReader<'r>> for BytesReader<'r> {
-    fn into(self) -> packed::BytesReader<'r> {
-        packed::BytesReader::new_unchecked(self.as_slice())
+/// Convert bytes::Bytes to schemas::basic::Bytes
+impl From<bytes::Bytes> for Bytes {
+    fn from(v: bytes::Bytes) -> Self {
+        Bytes::from(v.as_ref())
     }
 }
 
@@ -101,27 +97,59 @@ impl From<Bytes> for Vec<u8> {
 ///
 /// The main thing here is to remove the Header from the Molecule data.
 impl TryFrom<Bytes> for String {
-    type Error = FromUtf8Error;
-    fn try_from(v: Bytes) -> Result<Self, FromUtf8Error> {
+    type Error = DasTypesError;
+    fn try_from(v: Bytes) -> Result<Self, DasTypesError> {
         let bytes = v.as_reader().raw_data().to_vec();
-        String::from_utf8(bytes).map(|v| String::from(v))
+        String::from_utf8(bytes).map_err(|_| DasTypesError::InvalidUtf8("Bytes"))
     }
 }
 
-impl From<Script> for ScriptOpt {
-    fn from(v: Script) -> Self {
-        ScriptOpt::new_builder().set(Some(v)).build()
-    }
+/// Implement convert from the inner type to the option type
+macro_rules! impl_option_convert {
+    ($( $inner:ident => $option:ident ),+) => {
+        $(
+            impl From<$inner> for $option {
+                fn from(v: $inner) -> Self {
+                    $option::new_builder().set(Some(v)).build()
+                }
+            }
+        )+
+    };
+}
+
+impl_option_convert!(Script => ScriptOpt, Hash => HashOpt, DataEntity => DataEntityOpt);
+
+/// Implement convert from Vec of the items to the vector type
+macro_rules! impl_vec_convert {
+    ($( $item:ident => $vec:ident ),+) => {
+        $(
+            impl From<Vec<$item>> for $vec {
+                fn from(v: Vec<$item>) -> Self {
+                    $vec::new_builder().set(v).build()
+                }
+            }
+        )+
+    };
 }
 
+impl_vec_convert!(
+    Bytes => Chars,
+    PriceConfig => PriceConfigList,
+    SL => SliceList,
+    ProposalItem => SL,
+    IncomeRecord => IncomeRecords,
+    Record => Records,
+    AccountChar => AccountChars
+);
+
 /// Convert &[u8] to schemas::basic::Hash
 ///
 /// The difference with from_slice is that it does not require a dynvec header.
 impl TryFrom<&[u8]> for Hash {
-    type Error = VerificationError;
-    fn try_from(v: &[u8]) -> Result<Self, VerificationError> {
-        if v.len() != 32 {
-            return Err(VerificationError::TotalSizeNotMatch("Hash".to_owned(), 32, v.len()));
+    type Error = DasTypesError;
+    fn try_from(v: &[u8]) -> Result<Self, DasTypesError> {
+        if v.len() != Hash::TOTAL_SIZE {
+            return Err(DasTypesError::LengthNotMatch("Hash", Hash::TOTAL_SIZE, v.len()));
         }
         let mut inner = [Byte::new(0); 32];
         let v = v.to_owned().into_iter().map(Byte::new).collect::<Vec<_>>();
@@ -131,8 +159,8 @@ impl TryFrom<&[u8]> for Hash {
 }
 
 impl TryFrom<Vec<u8>> for Hash {
-    type Error = VerificationError;
-    fn try_from(v: Vec<u8>) -> Result<Self, VerificationError> {
+    type Error = DasTypesError;
+    fn try_from(v: Vec<u8>) -> Result<Self, DasTypesError> {
         Hash::try_from(v.as_slice())
     }
 }
@@ -147,31 +175,6 @@ impl From<[u8; 32]> for Hash {
     }
 }
 
-/// Convert between schemas::Hash and ckb_std::ckb_types::packed::Byte32
-impl From<packed::Byte32> for Hash {
-    fn from(v: packed::Byte32) -> Self {
-        Hash::new_unchecked(v.as_bytes().into())
-    }
-}
-
-impl<'r> From<packed::Byte32Reader<'r>> for HashReader<'r> {
-    fn from(v: packed::Byte32Reader<'r>) -> Self {
-        HashReader::new_unchecked(v.as_slice())
-    }
-}
-
-impl Into<packed::Byte32> for Hash {
-    fn into(self) -> packed::Byte32 {
-        packed::Byte32::new_unchecked(self.as_bytes().into())
-    }
-}
-
-impl<'r> Into<packed::Byte32Reader<'r>> for HashReader<'r> {
-    fn into(self) -> packed::Byte32Reader<'r> {
-        packed::Byte32Reader::new_unchecked(self.as_slice())
-    }
-}
-
 /// Convert schemas::basic::Hash to Vec<u8>
 impl From<Hash> for Vec<u8> {
     fn from(v: Hash) -> Self {
@@ -179,41 +182,16 @@ impl From<Hash> for Vec<u8> {
     }
 }
 
-/// Convert between schemas::Script and ckb_std::ckb_types::packed::Script
-impl From<packed::Script> for Script {
-    fn from(v: packed::Script) -> Self {
-        Script::new_unchecked(v.as_bytes().into())
-    }
-}
-
-impl<'r> From<packed::ScriptReader<'r>> for ScriptReader<'r> {
-    fn from(v: packed::ScriptReader<'r>) -> Self {
-        ScriptReader::new_unchecked(v.as_slice())
-    }
-}
-
-impl Into<packed::Script> for Script {
-    fn into(self) -> packed::Script {
-        packed::Script::new_unchecked(self.as_bytes().into())
-    }
-}
-
-impl<'r> Into<packed::ScriptReader<'r>> for ScriptReader<'r> {
-    fn into(self) -> packed::ScriptReader<'r> {
-        packed::ScriptReader::new_unchecked(self.as_slice())
-    }
-}
-
-/// Convert &[u8] to schemas::basic::Hash
+/// Convert &[u8] to schemas::basic::AccountId
 ///
 /// The difference with from_slice is that it does not require a dynvec header.
 impl TryFrom<&[u8]> for AccountId {
-    type Error = VerificationError;
-    fn try_from(v: &[u8]) -> Result<Self, VerificationError> {
-        if v.len() != 20 {
-            return Err(VerificationError::TotalSizeNotMatch(
-                "AccountId".to_owned(),
-                10,
+    type Error = DasTypesError;
+    fn try_from(v: &[u8]) -> Result<Self, DasTypesError> {
+        if v.len() != AccountId::TOTAL_SIZE {
+            return Err(DasTypesError::LengthNotMatch(
+                "AccountId",
+                AccountId::TOTAL_SIZE,
                 v.len(),
             ));
         }
@@ -225,12 +203,22 @@ impl TryFrom<&[u8]> for AccountId {
 }
 
 impl TryFrom<Vec<u8>> for AccountId {
-    type Error = VerificationError;
-    fn try_from(v: Vec<u8>) -> Result<Self, VerificationError> {
+    type Error = DasTypesError;
+    fn try_from(v: Vec<u8>) -> Result<Self, DasTypesError> {
         AccountId::try_from(v.as_slice())
     }
 }
 
+impl From<[u8; 20]> for AccountId {
+    fn from(v: [u8; 20]) -> Self {
+        let mut inner = [Byte::new(0); 20];
+        for (i, item) in v.iter().enumerate() {
+            inner[i] = Byte::new(*item);
+        }
+        Self::new_builder().set(inner).build()
+    }
+}
+
 /// Convert schemas::basic::AccountId to Vec<u8>
 impl From<AccountId> for Vec<u8> {
     fn from(v: AccountId) -> Self {
@@ -261,3 +249,89 @@ impl<'r> AccountCharsReader<'r> {
         ret
     }
 }
+
+/// Conversions with the packed types of CKB, available when either `ckb-std` or `ckb-types` is enabled.
+#[cfg(any(feature = "ckb-std", feature = "ckb-types"))]
+mod ckb {
+    use super::super::schemas::packed::*;
+    #[cfg(feature = "ckb-std")]
+    use ckb_std::ckb_types::packed;
+    #[cfg(all(feature = "ckb-types", not(feature = "ckb-std")))]
+    use ckb_types::packed;
+    use molecule::prelude::*;
+
+    /// Convert between schemas::Bytes and packed::Bytes
+    impl From<packed::Bytes> for Bytes {
+        fn from(v: packed::Bytes) -> Self {
+            Bytes::new_unchecked(v.as_bytes().into())
+        }
+    }
+
+    impl Into<packed::Bytes> for Bytes {
+        fn into(self) -> packed::Bytes {
+            packed::Bytes::new_unchecked(self.as_bytes().into())
+        }
+    }
+
+    impl<'r> From<packed::BytesReader<'r>> for BytesReader<'r> {
+        fn from(v: packed::BytesReader<'r>) -> Self {
+            BytesReader::new_unchecked(v.as_slice())
+        }
+    }
+
+    impl<'r> Into<packed::BytesReader<'r>> for BytesReader<'r> {
+        fn into(self) -> packed::BytesReader<'r> {
+            packed::BytesReader::new_unchecked(self.as_slice())
+        }
+    }
+
+    /// Convert between schemas::Hash and packed::Byte32
+    impl From<packed::Byte32> for Hash {
+        fn from(v: packed::Byte32) -> Self {
+            Hash::new_unchecked(v.as_bytes().into())
+        }
+    }
+
+    impl<'r> From<packed::Byte32Reader<'r>> for HashReader<'r> {
+        fn from(v: packed::Byte32Reader<'r>) -> Self {
+            HashReader::new_unchecked(v.as_slice())
+        }
+    }
+
+    impl Into<packed::Byte32> for Hash {
+        fn into(self) -> packed::Byte32 {
+            packed::Byte32::new_unchecked(self.as_bytes().into())
+        }
+    }
+
+    impl<'r> Into<packed::Byte32Reader<'r>> for HashReader<'r> {
+        fn into(self) -> packed::Byte32Reader<'r> {
+            packed::Byte32Reader::new_unchecked(self.as_slice())
+        }
+    }
+
+    /// Convert between schemas::Script and packed::Script
+    impl From<packed::Script> for Script {
+        fn from(v: packed::Script) -> Self {
+            Script::new_unchecked(v.as_bytes().into())
+        }
+    }
+
+    impl<'r> From<packed::ScriptReader<'r>> for ScriptReader<'r> {
+        fn from(v: packed::ScriptReader<'r>) -> Self {
+            ScriptReader::new_unchecked(v.as_slice())
+        }
+    }
+
+    impl Into<packed::Script> for Script {
+        fn into(self) -> packed::Script {
+            packed::Script::new_unchecked(self.as_bytes().into())
+        }
+    }
+
+    impl<'r> Into<packed::ScriptReader<'r>> for ScriptReader<'r> {
+        fn into(self) -> packed::ScriptReader<'r> {
+            packed::ScriptReader::new_unchecked(self.as_slice())
+        }
+    }
+}
@@ -0,0 +1,720 @@
+use super::{error::DasTypesError, schemas::packed::*};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use molecule::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Modified,
+    // The item is only in the new vector.
+    Added,
+    // The item is only in the old vector.
+    Removed,
+}
+
+/// A changed field, the path is like `records[3].record_value`.
+///
+/// The extra fields of a table read in compatible mode have no names, a change in them is reported as the path of the
+/// table followed by `..`, like `records[3]..`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+/// Compare two versions of the same data structurally.
+///
+/// Tables and vectors are walked field by field and item by item, so a change in records is reported like
+/// `records[3].record_value` instead of only `records`.
+///
+/// The other version is a type parameter only for comparing data with different lifetimes, like the mixers of two
+/// witnesses.
+pub trait Diff<Other: ?Sized = Self> {
+    /// Compare with the other version which is at the path of the outermost data, changes are appended to changes.
+    fn diff_at(&self, other: &Other, path: &mut String, changes: &mut Vec<FieldChange>);
+}
+
+/// The names of the fields of a table, in the order of the schema.
+pub trait FieldNames {
+    const FIELD_NAMES: &'static [&'static str];
+}
+
+/// Return all changes from old to new.
+pub fn diff<T: Diff<U> + ?Sized, U: ?Sized>(old: &T, new: &U) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    old.diff_at(new, &mut String::new(), &mut changes);
+    changes
+}
+
+/// Return an error with the path of the first change which is not allowed.
+///
+/// A change is allowed when its path is one of the allowed paths or inside them, e.g. `records` allows
+/// `records[3].record_value`.
+pub fn assert_only_changed<T: Diff<U> + ?Sized, U: ?Sized>(
+    old: &T,
+    new: &U,
+    allowed: &[&str],
+) -> Result<(), DasTypesError> {
+    let is_allowed = |path: &str| {
+        allowed.iter().any(|allowed| {
+            path.strip_prefix(allowed)
+                .map(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+                .unwrap_or(false)
+        })
+    };
+
+    match diff(old, new).into_iter().find(|change| !is_allowed(&change.path)) {
+        Some(change) => Err(DasTypesError::FieldChanged(change.path)),
+        None => Ok(()),
+    }
+}
+
+/// Compare a field of tables, the path is extended by the name of the field while it is compared.
+pub(crate) fn diff_field<T: Diff<U> + ?Sized, U: ?Sized>(
+    name: &str,
+    old: &T,
+    new: &U,
+    path: &mut String,
+    changes: &mut Vec<FieldChange>,
+) {
+    let path_len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(name);
+    old.diff_at(new, path, changes);
+    path.truncate(path_len);
+}
+
+/// Record a change of a field without comparing it, like a field which only exists in one version of a table.
+pub(crate) fn push_field_change(name: &str, kind: ChangeKind, path: &mut String, changes: &mut Vec<FieldChange>) {
+    let path_len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(name);
+    changes.push(FieldChange {
+        path: path.clone(),
+        kind,
+    });
+    path.truncate(path_len);
+}
+
+fn diff_items<T: Diff<U>, U>(
+    old: impl Iterator<Item = T>,
+    new: impl Iterator<Item = U>,
+    path: &mut String,
+    changes: &mut Vec<FieldChange>,
+) {
+    let (mut old, mut new) = (old.fuse(), new.fuse());
+    for index in 0.. {
+        let path_len = path.len();
+        let _ = write!(path, "[{}]", index);
+        match (old.next(), new.next()) {
+            (Some(old_item), Some(new_item)) => old_item.diff_at(&new_item, path, changes),
+            (Some(_), None) => changes.push(FieldChange {
+                path: path.clone(),
+                kind: ChangeKind::Removed,
+            }),
+            (None, Some(_)) => changes.push(FieldChange {
+                path: path.clone(),
+                kind: ChangeKind::Added,
+            }),
+            (None, None) => break,
+        }
+        path.truncate(path_len);
+    }
+}
+
+/// Types which are compared as a whole.
+macro_rules! impl_diff_leaf {
+    ($( $reader:ident ),+) => {
+        $(
+            impl<'a, 'b> Diff<$reader<'b>> for $reader<'a> {
+                fn diff_at(&self, other: &$reader<'b>, path: &mut String, changes: &mut Vec<FieldChange>) {
+                    if self.as_slice() != other.as_slice() {
+                        changes.push(FieldChange {
+                            path: path.clone(),
+                            kind: ChangeKind::Modified,
+                        });
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_diff_option {
+    ($( $reader:ident ),+) => {
+        $(
+            impl<'a, 'b> Diff<$reader<'b>> for $reader<'a> {
+                fn diff_at(&self, other: &$reader<'b>, path: &mut String, changes: &mut Vec<FieldChange>) {
+                    match (self.to_opt(), other.to_opt()) {
+                        (Some(old), Some(new)) => old.diff_at(&new, path, changes),
+                        (None, None) => {}
+                        _ => changes.push(FieldChange {
+                            path: path.clone(),
+                            kind: ChangeKind::Modified,
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_diff_vec {
+    ($( $reader:ident ),+) => {
+        $(
+            impl<'a, 'b> Diff<$reader<'b>> for $reader<'a> {
+                fn diff_at(&self, other: &$reader<'b>, path: &mut String, changes: &mut Vec<FieldChange>) {
+                    diff_items(self.iter(), other.iter(), path, changes);
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_diff_table {
+    ($( $reader:ident { $( $field:ident ),+ } );+) => {
+        $(
+            impl<'a, 'b> Diff<$reader<'b>> for $reader<'a> {
+                fn diff_at(&self, other: &$reader<'b>, path: &mut String, changes: &mut Vec<FieldChange>) {
+                    let changes_len = changes.len();
+                    $(diff_field(stringify!($field), &self.$field(), &other.$field(), path, changes);)+
+
+                    // Every field is the same, so the bytes can only differ in the extra fields.
+                    if changes.len() == changes_len && self.as_slice() != other.as_slice() {
+                        changes.push(FieldChange {
+                            path: alloc::format!("{}..", path),
+                            kind: ChangeKind::Modified,
+                        });
+                    }
+                }
+            }
+
+            impl<'r> FieldNames for $reader<'r> {
+                const FIELD_NAMES: &'static [&'static str] = &[$( stringify!($field) ),+];
+            }
+        )+
+    };
+}
+
+impl_diff_leaf!(
+    ByteReader,
+    Uint8Reader,
+    Uint32Reader,
+    Uint64Reader,
+    BytesReader,
+    TimestampReader,
+    HashReader,
+    OutPointReader,
+    AccountIdReader,
+    AccountCharsReader
+);
+
+impl_diff_option!(HashOptReader, ScriptOptReader, DataEntityOptReader);
+
+impl_diff_vec!(
+    CharsReader,
+    PriceConfigListReader,
+    SliceListReader,
+    SLReader,
+    IncomeRecordsReader,
+    RecordsReader
+);
+
+impl_diff_table!(
+    ScriptReader {
+        code_hash,
+        hash_type,
+        args
+    };
+    DataReader {
+        dep,
+        old,
+        new
+    };
+    DataEntityReader {
+        index,
+        version,
+        entity
+    };
+    ActionDataReader {
+        action,
+        params
+    };
+    ConfigCellMainReader {
+        status,
+        type_id_table,
+        das_lock_out_point_table
+    };
+    TypeIdTableReader {
+        account_cell,
+        apply_register_cell,
+        balance_cell,
+        income_cell,
+        pre_account_cell,
+        proposal_cell,
+        account_sale_cell,
+        account_auction_cell,
+        offer_cell,
+        reverse_record_cell,
+        sub_account_cell
+    };
+    DasLockOutPointTableReader {
+        ckb_signall,
+        ckb_multisign,
+        ckb_anyone_can_pay,
+        eth,
+        tron,
+        ed25519
+    };
+    ConfigCellAccountReader {
+        max_length,
+        basic_capacity,
+        prepared_fee_capacity,
+        expiration_grace_period,
+        record_min_ttl,
+        record_size_limit,
+        transfer_account_fee,
+        edit_manager_fee,
+        edit_records_fee,
+        common_fee,
+        transfer_account_throttle,
+        edit_manager_throttle,
+        edit_records_throttle,
+        common_throttle
+    };
+    ConfigCellApplyReader {
+        apply_min_waiting_block_number,
+        apply_max_waiting_block_number
+    };
+    ConfigCellPriceReader {
+        discount,
+        prices
+    };
+    DiscountConfigReader {
+        invited_discount
+    };
+    PriceConfigReader {
+        length,
+        new,
+        renew
+    };
+    ConfigCellProposalReader {
+        proposal_min_confirm_interval,
+        proposal_min_extend_interval,
+        proposal_min_recycle_interval,
+        proposal_max_account_affect,
+        proposal_max_pre_account_contain
+    };
+    ConfigCellProfitRateReader {
+        inviter,
+        channel,
+        proposal_create,
+        proposal_confirm,
+        income_consolidate,
+        sale_buyer_inviter,
+        sale_buyer_channel,
+        sale_das,
+        auction_bidder_inviter,
+        auction_bidder_channel,
+        auction_das,
+        auction_prev_bidder
+    };
+    ConfigCellIncomeReader {
+        basic_capacity,
+        max_records,
+        min_transfer_capacity
+    };
+    ConfigCellReleaseReader {
+        lucky_number
+    };
+    ConfigCellSecondaryMarketReader {
+        common_fee,
+        sale_min_price,
+        sale_expiration_limit,
+        sale_description_bytes_limit,
+        sale_cell_basic_capacity,
+        sale_cell_prepared_fee_capacity,
+        auction_max_extendable_duration,
+        auction_duration_increment_each_bid,
+        auction_min_opening_price,
+        auction_min_increment_rate_each_bid,
+        auction_description_bytes_limit,
+        auction_cell_basic_capacity,
+        auction_cell_prepared_fee_capacity,
+        offer_min_price,
+        offer_cell_basic_capacity,
+        offer_cell_prepared_fee_capacity,
+        offer_message_bytes_limit
+    };
+    ConfigCellReverseResolutionReader {
+        record_basic_capacity,
+        record_prepared_fee_capacity,
+        common_fee
+    };
+    ConfigCellSubAccountReader {
+        basic_capacity,
+        prepared_fee_capacity,
+        new_sub_account_price,
+        renew_sub_account_price,
+        common_fee,
+        create_fee,
+        edit_fee,
+        renew_fee,
+        recycle_fee
+    };
+    ProposalCellDataReader {
+        proposer_lock,
+        created_at_height,
+        slices
+    };
+    ProposalItemReader {
+        account_id,
+        item_type,
+        next
+    };
+    IncomeCellDataReader {
+        creator,
+        records
+    };
+    IncomeRecordReader {
+        belong_to,
+        capacity
+    };
+    AccountCellDataV1Reader {
+        id,
+        account,
+        registered_at,
+        updated_at,
+        status,
+        records
+    };
+    AccountCellDataV2Reader {
+        id,
+        account,
+        registered_at,
+        last_transfer_account_at,
+        last_edit_manager_at,
+        last_edit_records_at,
+        status,
+        records
+    };
+    AccountCellDataReader {
+        id,
+        account,
+        registered_at,
+        last_transfer_account_at,
+        last_edit_manager_at,
+        last_edit_records_at,
+        status,
+        records,
+        enable_sub_account,
+        renew_sub_account_price
+    };
+    RecordReader {
+        record_type,
+        record_key,
+        record_label,
+        record_value,
+        record_ttl
+    };
+    AccountSaleCellDataV1Reader {
+        account_id,
+        account,
+        price,
+        description,
+        started_at
+    };
+    AccountSaleCellDataReader {
+        account_id,
+        account,
+        price,
+        description,
+        started_at,
+        buyer_inviter_profit_rate
+    };
+    AccountAuctionCellDataReader {
+        account_id,
+        account,
+        description,
+        opening_price,
+        increment_rate_each_bid,
+        started_at,
+        ended_at,
+        current_bidder_lock,
+        current_bid_price,
+        prev_bidder_profit_rate
+    };
+    PreAccountCellDataReader {
+        account,
+        refund_lock,
+        owner_lock_args,
+        inviter_id,
+        inviter_lock,
+        channel_lock,
+        price,
+        quote,
+        invited_discount,
+        created_at
+    };
+    AccountCharReader {
+        char_set_name,
+        bytes
+    };
+    OfferCellDataReader {
+        account,
+        price,
+        message,
+        inviter_lock,
+        channel_lock
+    };
+    SubAccountReader {
+        lock,
+        id,
+        account,
+        suffix,
+        registered_at,
+        expired_at,
+        status,
+        records,
+        nonce,
+        enable_sub_account,
+        renew_sub_account_price
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mixer::account_cell_data_reader_mixer, test_util::append_field};
+    use alloc::vec;
+
+    fn record(key: &[u8]) -> Record {
+        Record::new_builder().record_key(Bytes::from(key)).build()
+    }
+
+    fn records(keys: &[&[u8]]) -> Records {
+        Records::new_builder()
+            .set(keys.iter().map(|key| record(key)).collect())
+            .build()
+    }
+
+    fn change(path: &str, kind: ChangeKind) -> FieldChange {
+        FieldChange {
+            path: String::from(path),
+            kind,
+        }
+    }
+
+    #[test]
+    fn same_data_has_no_change() {
+        let data = AccountCellData::new_builder().records(records(&[b"a"])).build();
+        assert!(diff(&data.as_reader(), &data.as_reader()).is_empty());
+        assert!(assert_only_changed(&data.as_reader(), &data.as_reader(), &[]).is_ok());
+    }
+
+    #[test]
+    fn nested_changes_have_paths() {
+        let old = AccountCellData::new_builder().records(records(&[b"a", b"b"])).build();
+        let new = old
+            .clone()
+            .as_builder()
+            .records(records(&[b"a", b"c", b"d"]))
+            .last_edit_records_at(Uint64::from(1))
+            .build();
+
+        assert_eq!(
+            diff(&old.as_reader(), &new.as_reader()),
+            vec![
+                change("last_edit_records_at", ChangeKind::Modified),
+                change("records[1].record_key", ChangeKind::Modified),
+                change("records[2]", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff(&new.as_reader(), &old.as_reader()),
+            vec![
+                change("last_edit_records_at", ChangeKind::Modified),
+                change("records[1].record_key", ChangeKind::Modified),
+                change("records[2]", ChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn options_are_compared_by_inner_fields() {
+        let lock = Script::new_builder().args(Bytes::from(&b"a"[..])).build();
+        let none = PreAccountCellData::default();
+        let some = none
+            .clone()
+            .as_builder()
+            .inviter_lock(ScriptOpt::new_builder().set(Some(lock.clone())).build())
+            .build();
+        let other = none
+            .clone()
+            .as_builder()
+            .inviter_lock(
+                ScriptOpt::new_builder()
+                    .set(Some(lock.as_builder().args(Bytes::from(&b"b"[..])).build()))
+                    .build(),
+            )
+            .build();
+
+        assert_eq!(
+            diff(&none.as_reader(), &some.as_reader()),
+            vec![change("inviter_lock", ChangeKind::Modified)]
+        );
+        assert_eq!(
+            diff(&some.as_reader(), &other.as_reader()),
+            vec![change("inviter_lock.args", ChangeKind::Modified)]
+        );
+    }
+
+    #[test]
+    fn allowed_paths_match_whole_segments() {
+        let old = AccountCellData::new_builder().records(records(&[b"a"])).build();
+        let new = old.clone().as_builder().records(records(&[b"b"])).build();
+
+        assert!(assert_only_changed(&old.as_reader(), &new.as_reader(), &["records"]).is_ok());
+        assert!(assert_only_changed(&old.as_reader(), &new.as_reader(), &["records[0]"]).is_ok());
+        assert!(assert_only_changed(&old.as_reader(), &new.as_reader(), &["records[0].record_key"]).is_ok());
+        for allowed in ["record", "records[0].record", "status"].iter() {
+            match assert_only_changed(&old.as_reader(), &new.as_reader(), &[allowed]) {
+                Err(DasTypesError::FieldChanged(path)) => assert_eq!(path, "records[0].record_key"),
+                other => panic!("{:?} should not be allowed, but it is {:?}", allowed, other),
+            }
+        }
+    }
+
+    #[test]
+    fn mixers_of_same_version_compare_all_fields() {
+        let old = AccountCellData::default();
+        let new = old.clone().as_builder().enable_sub_account(Uint8::from(1)).build();
+        let old = account_cell_data_reader_mixer(3, old.as_slice()).unwrap();
+        let new = account_cell_data_reader_mixer(3, new.as_slice()).unwrap();
+
+        assert_eq!(
+            diff(&*old, &*new),
+            vec![change("enable_sub_account", ChangeKind::Modified)]
+        );
+        assert!(assert_only_changed(&*old, &*new, &["enable_sub_account"]).is_ok());
+    }
+
+    #[test]
+    fn mixers_of_different_versions_report_version_specific_fields() {
+        let v2 = AccountCellDataV2::new_builder().records(records(&[b"a"])).build();
+        let latest = AccountCellData::new_builder()
+            .records(records(&[b"b"]))
+            .enable_sub_account(Uint8::from(1))
+            .build();
+        let v2 = account_cell_data_reader_mixer(2, v2.as_slice()).unwrap();
+        let latest = account_cell_data_reader_mixer(3, latest.as_slice()).unwrap();
+
+        assert_eq!(
+            diff(&*v2, &*latest),
+            vec![
+                change("version", ChangeKind::Modified),
+                change("records[0].record_key", ChangeKind::Modified),
+                change("enable_sub_account", ChangeKind::Added),
+                change("renew_sub_account_price", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff(&*latest, &*v2),
+            vec![
+                change("version", ChangeKind::Modified),
+                change("records[0].record_key", ChangeKind::Modified),
+                change("enable_sub_account", ChangeKind::Removed),
+                change("renew_sub_account_price", ChangeKind::Removed),
+            ]
+        );
+
+        // An upgrade must be allowed explicitly, with the fields it adds.
+        match assert_only_changed(&*v2, &*latest, &["records"]) {
+            Err(DasTypesError::FieldChanged(path)) => assert_eq!(path, "version"),
+            other => panic!("{:?}", other),
+        }
+        match assert_only_changed(&*v2, &*latest, &["records", "version"]) {
+            Err(DasTypesError::FieldChanged(path)) => assert_eq!(path, "enable_sub_account"),
+            other => panic!("{:?}", other),
+        }
+        assert!(assert_only_changed(
+            &*v2,
+            &*latest,
+            &["records", "version", "enable_sub_account", "renew_sub_account_price"]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn mixers_compare_optional_fields_of_versions_having_them() {
+        let v1 = AccountCellDataV1::new_builder().updated_at(Uint64::from(1)).build();
+        let v2 = AccountCellDataV2::new_builder()
+            .last_edit_records_at(Uint64::from(1))
+            .build();
+        let latest = AccountCellData::new_builder()
+            .last_edit_records_at(Uint64::from(2))
+            .build();
+        let v1 = account_cell_data_reader_mixer(1, v1.as_slice()).unwrap();
+        let v2 = account_cell_data_reader_mixer(2, v2.as_slice()).unwrap();
+        let latest = account_cell_data_reader_mixer(3, latest.as_slice()).unwrap();
+
+        assert_eq!(
+            diff(&*v2, &*latest),
+            vec![
+                change("version", ChangeKind::Modified),
+                change("last_edit_records_at", ChangeKind::Modified),
+                change("enable_sub_account", ChangeKind::Added),
+                change("renew_sub_account_price", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff(&*v1, &*v2),
+            vec![
+                change("version", ChangeKind::Modified),
+                change("last_transfer_account_at", ChangeKind::Added),
+                change("last_edit_manager_at", ChangeKind::Added),
+                change("last_edit_records_at", ChangeKind::Added),
+                change("updated_at", ChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_fields_of_compatible_tables_are_reported() {
+        let data = AccountCellData::default();
+        let old = append_field(data.as_slice(), &[1]);
+        let new = append_field(data.as_slice(), &[2]);
+        let old = AccountCellDataReader::from_compatible_slice(&old).unwrap();
+        let new = AccountCellDataReader::from_compatible_slice(&new).unwrap();
+
+        assert_eq!(diff(&old, &new), vec![change("..", ChangeKind::Modified)]);
+        assert_eq!(diff(&data.as_reader(), &new), vec![change("..", ChangeKind::Modified)]);
+        match assert_only_changed(&old, &new, &["records"]) {
+            Err(DasTypesError::FieldChanged(path)) => assert_eq!(path, ".."),
+            other => panic!("{:?}", other),
+        }
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn nested_extra_fields_are_reported_with_paths() {
+        let extended = Record::new_unchecked(append_field(record(b"a").as_slice(), &[1]).into());
+        let old = AccountCellData::new_builder().records(records(&[b"a"])).build();
+        let new = AccountCellData::new_builder()
+            .records(Records::new_builder().push(extended).build())
+            .build();
+        let new = AccountCellDataReader::from_compatible_slice(new.as_slice()).unwrap();
+
+        assert_eq!(
+            diff(&old.as_reader(), &new),
+            vec![change("records[0]..", ChangeKind::Modified)]
+        );
+        assert!(assert_only_changed(&old.as_reader(), &new, &["records"]).is_ok());
+        assert!(assert_only_changed(&old.as_reader(), &new, &["records[0]"]).is_ok());
+        assert!(assert_only_changed(&old.as_reader(), &new, &["records[1]"]).is_err());
+    }
+}
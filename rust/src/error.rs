@@ -0,0 +1,112 @@
+#[cfg(feature = "calculator")]
+use super::auction::AuctionError;
+#[cfg(feature = "cell-data")]
+use super::cell_data::CellDataError;
+use super::constants::{DataType, DataTypeCategory};
+#[cfg(feature = "calculator")]
+use super::offer::OfferError;
+use alloc::string::String;
+use core::fmt;
+use molecule::error::VerificationError;
+
+/// The error of all fallible conversions and helpers in this crate.
+///
+/// Scripts should handle it rather than panic, a panic in a type script is only an opaque failure on chain.
+// VerificationError of molecule is only Debug, so is this one.
+#[derive(Debug)]
+pub enum DasTypesError {
+    // The number is not any variant of the enum, the fields are (enum name, number).
+    UnknownValue(&'static str, u64),
+    // The string is not any variant name of the enum, the fields are (enum name, string).
+    UnknownName(&'static str, String),
+    // The DataType is not in the category which is required.
+    DataTypeNotMatch(DataType, DataTypeCategory),
+    // The index is out of the range of the type, the fields are (type name, index).
+    OutOfRange(&'static str, usize),
+    // The length of bytes is not the one of the type, the fields are (type name, expected, actual).
+    LengthNotMatch(&'static str, usize, usize),
+    // The bytes of the type are not valid UTF-8.
+    InvalidUtf8(&'static str),
+    // The DataType has no versioned table registered.
+    Unregistered(DataType),
+    // The version of the table is unknown, the fields are (table name, version).
+    UnknownVersion(&'static str, u32),
+    // The table is not in the version which is required, the fields are (table name, expected, actual).
+    VersionNotMatch(&'static str, u32, u32),
+    // The molecule data can not pass the verification.
+    Verification(VerificationError),
+    // The field of the molecule data can not pass the verification, the fields are (path, byte offset, error).
+    VerificationAt(String, usize, VerificationError),
+    // The field at the path is changed, but it is not allowed to.
+    FieldChanged(String),
+    // The bytes are not a DAS witness with a known DataType.
+    InvalidWitness,
+    // The JSON document can not be converted, the field is the message of the JSON parser.
+    Json(String),
+    // The witness of the config or the data of its ConfigCell is not provided.
+    ConfigMissing(DataType),
+    // The hash of the witness is not the one stored in the data of the ConfigCell.
+    ConfigHashNotMatch(DataType),
+    // The data can not be converted back to the same bytes, like a table with the extra fields of compatible mode.
+    LossyConversion(&'static str),
+    // The bid can not be placed on the auction.
+    #[cfg(feature = "calculator")]
+    Auction(AuctionError),
+    // The offer can not be created or accepted.
+    #[cfg(feature = "calculator")]
+    Offer(OfferError),
+    // The raw cell data is malformed or does not match its witness.
+    #[cfg(feature = "cell-data")]
+    CellData(CellDataError),
+}
+
+impl From<VerificationError> for DasTypesError {
+    fn from(err: VerificationError) -> Self {
+        DasTypesError::Verification(err)
+    }
+}
+
+impl fmt::Display for DasTypesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DasTypesError::UnknownValue(name, value) => write!(f, "{} has no variant of value {}", name, value),
+            DasTypesError::UnknownName(name, value) => write!(f, "{} has no variant named {:?}", name, value),
+            DasTypesError::DataTypeNotMatch(data_type, category) => {
+                write!(f, "DataType {} is not in category {:?}", data_type, category)
+            }
+            DasTypesError::OutOfRange(name, index) => write!(f, "{} is out of range at index {}", name, index),
+            DasTypesError::LengthNotMatch(name, expected, actual) => {
+                write!(f, "{} should be {} bytes, but it is {} bytes", name, expected, actual)
+            }
+            DasTypesError::InvalidUtf8(name) => write!(f, "{} is not valid UTF-8", name),
+            DasTypesError::Unregistered(data_type) => write!(f, "DataType {} has no table registered", data_type),
+            DasTypesError::UnknownVersion(name, version) => write!(f, "{} has no version {}", name, version),
+            DasTypesError::VersionNotMatch(name, expected, actual) => {
+                write!(
+                    f,
+                    "{} should be version {}, but it is version {}",
+                    name, expected, actual
+                )
+            }
+            DasTypesError::Verification(err) => write!(f, "{}", err),
+            DasTypesError::VerificationAt(path, offset, err) => write!(f, "{} at byte {}: {}", path, offset, err),
+            DasTypesError::FieldChanged(path) => write!(f, "{} should not be changed", path),
+            DasTypesError::InvalidWitness => write!(f, "not a DAS witness"),
+            DasTypesError::Json(message) => write!(f, "invalid JSON: {}", message),
+            DasTypesError::ConfigMissing(data_type) => write!(f, "{} is not provided", data_type),
+            DasTypesError::ConfigHashNotMatch(data_type) => {
+                write!(f, "{} does not match the hash in its cell data", data_type)
+            }
+            DasTypesError::LossyConversion(name) => write!(f, "{} can not be converted without losing bytes", name),
+            #[cfg(feature = "calculator")]
+            DasTypesError::Auction(err) => write!(f, "invalid bid: {:?}", err),
+            #[cfg(feature = "calculator")]
+            DasTypesError::Offer(err) => write!(f, "invalid offer: {:?}", err),
+            #[cfg(feature = "cell-data")]
+            DasTypesError::CellData(err) => write!(f, "invalid cell data: {:?}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DasTypesError {}
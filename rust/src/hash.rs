@@ -0,0 +1,153 @@
+use super::{constants::HASH_BYTES, schemas::packed::*, util::blake2b_256};
+use molecule::prelude::*;
+
+/// The blake2b hash with CKB personalization of the molecule encoded bytes.
+///
+/// This is the hash DAS cells store at the head of their data for the entities in witnesses.
+pub trait EntityHash {
+    fn entity_hash(&self) -> [u8; HASH_BYTES];
+}
+
+macro_rules! impl_entity_hash {
+    ($( $entity:ident, $reader:ident );+) => {
+        $(
+            impl EntityHash for $entity {
+                fn entity_hash(&self) -> [u8; HASH_BYTES] {
+                    blake2b_256(self.as_slice())
+                }
+            }
+
+            impl<'r> EntityHash for $reader<'r> {
+                fn entity_hash(&self) -> [u8; HASH_BYTES] {
+                    blake2b_256(self.as_slice())
+                }
+            }
+        )+
+    };
+}
+
+impl_entity_hash!(
+    Uint8, Uint8Reader;
+    Uint32, Uint32Reader;
+    Uint64, Uint64Reader;
+    Bytes, BytesReader;
+    Timestamp, TimestampReader;
+    Hash, HashReader;
+    HashOpt, HashOptReader;
+    Script, ScriptReader;
+    ScriptOpt, ScriptOptReader;
+    OutPoint, OutPointReader;
+    Data, DataReader;
+    DataEntity, DataEntityReader;
+    DataEntityOpt, DataEntityOptReader;
+    ActionData, ActionDataReader;
+    ConfigCellMain, ConfigCellMainReader;
+    TypeIdTable, TypeIdTableReader;
+    DasLockOutPointTable, DasLockOutPointTableReader;
+    ConfigCellAccount, ConfigCellAccountReader;
+    ConfigCellApply, ConfigCellApplyReader;
+    Chars, CharsReader;
+    ConfigCellPrice, ConfigCellPriceReader;
+    DiscountConfig, DiscountConfigReader;
+    PriceConfigList, PriceConfigListReader;
+    PriceConfig, PriceConfigReader;
+    ConfigCellProposal, ConfigCellProposalReader;
+    ConfigCellProfitRate, ConfigCellProfitRateReader;
+    ConfigCellIncome, ConfigCellIncomeReader;
+    ConfigCellRelease, ConfigCellReleaseReader;
+    ConfigCellSecondaryMarket, ConfigCellSecondaryMarketReader;
+    ConfigCellReverseResolution, ConfigCellReverseResolutionReader;
+    ConfigCellSubAccount, ConfigCellSubAccountReader;
+    ProposalCellData, ProposalCellDataReader;
+    SliceList, SliceListReader;
+    SL, SLReader;
+    ProposalItem, ProposalItemReader;
+    IncomeCellData, IncomeCellDataReader;
+    IncomeRecords, IncomeRecordsReader;
+    IncomeRecord, IncomeRecordReader;
+    AccountCellDataV1, AccountCellDataV1Reader;
+    AccountCellDataV2, AccountCellDataV2Reader;
+    AccountCellData, AccountCellDataReader;
+    AccountId, AccountIdReader;
+    Record, RecordReader;
+    Records, RecordsReader;
+    AccountSaleCellDataV1, AccountSaleCellDataV1Reader;
+    AccountSaleCellData, AccountSaleCellDataReader;
+    AccountAuctionCellData, AccountAuctionCellDataReader;
+    PreAccountCellData, PreAccountCellDataReader;
+    AccountChars, AccountCharsReader;
+    AccountChar, AccountCharReader;
+    OfferCellData, OfferCellDataReader;
+    SubAccount, SubAccountReader
+);
+
+/// Check if the cell data starts with the hash of the entity wrapped in the DataEntity.
+///
+/// The hash is calculated from the raw entity bytes, so it equals to `entity_hash()` of the entity itself, like
+/// AccountCellData, rather than the DataEntity.
+pub fn verify_cell_data_hash(cell_data: &[u8], entity: &DataEntityReader) -> bool {
+    cell_data.len() >= HASH_BYTES && blake2b_256(entity.entity().raw_data())[..] == cell_data[..HASH_BYTES]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    // The blake2b-256 hash of empty input with the "ckb-default-hash" personalization.
+    const EMPTY_HASH: [u8; HASH_BYTES] = [
+        0x44, 0xf4, 0xc6, 0x97, 0x44, 0xd5, 0xf8, 0xc5, 0x5d, 0x64, 0x20, 0x62, 0x94, 0x9d, 0xca, 0xe4, 0x9b, 0xc4,
+        0xe7, 0xef, 0x43, 0xd3, 0x88, 0xc5, 0xa1, 0x2f, 0x42, 0xb5, 0x63, 0x3d, 0x16, 0x3e,
+    ];
+
+    fn data_entity(entity: &[u8]) -> DataEntity {
+        DataEntity::new_builder()
+            .version(Uint32::from(3))
+            .index(Uint32::from(0))
+            .entity(Bytes::from(entity))
+            .build()
+    }
+
+    #[test]
+    fn ckb_personalization() {
+        assert_eq!(blake2b_256(&[]), EMPTY_HASH);
+        assert_ne!(blake2b_256(&[0]), EMPTY_HASH);
+    }
+
+    #[test]
+    fn entity_hash_of_entity_and_reader() {
+        let data = AccountCellData::new_builder()
+            .registered_at(Uint64::from(1_700_000_000u64))
+            .build();
+        assert_eq!(data.entity_hash(), data.as_reader().entity_hash());
+        assert_eq!(data.entity_hash(), blake2b_256(data.as_slice()));
+
+        let record = Record::default();
+        assert_eq!(record.entity_hash(), record.as_reader().entity_hash());
+        assert_ne!(record.entity_hash(), data.entity_hash());
+    }
+
+    #[test]
+    fn verify_cell_data_hash_of_wrapped_entity() {
+        let data = AccountCellData::default();
+        let entity = data_entity(data.as_slice());
+
+        let mut cell_data = data.entity_hash().to_vec();
+        assert!(verify_cell_data_hash(&cell_data, &entity.as_reader()));
+        // The data may have more bytes after the hash.
+        cell_data.extend_from_slice(&[1u8; 8]);
+        assert!(verify_cell_data_hash(&cell_data, &entity.as_reader()));
+
+        // The hash of the DataEntity is not the hash of the entity.
+        let cell_data = entity.entity_hash().to_vec();
+        assert!(!verify_cell_data_hash(&cell_data, &entity.as_reader()));
+
+        let mut cell_data = data.entity_hash().to_vec();
+        cell_data[HASH_BYTES - 1] ^= 1;
+        assert!(!verify_cell_data_hash(&cell_data, &entity.as_reader()));
+
+        let cell_data: Vec<u8> = data.entity_hash()[..HASH_BYTES - 1].to_vec();
+        assert!(!verify_cell_data_hash(&cell_data, &entity.as_reader()));
+        assert!(!verify_cell_data_hash(&[], &entity.as_reader()));
+    }
+}
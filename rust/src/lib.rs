@@ -1,14 +1,46 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+#[cfg(feature = "calculator")]
+pub mod auction;
+#[cfg(feature = "cell-data")]
+pub mod cell_data;
+#[cfg(feature = "compatible")]
+pub mod compatible;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "json")]
+pub mod config_json;
 pub mod constants;
 pub mod convert;
+#[cfg(feature = "diff")]
+pub mod diff;
+pub mod error;
+#[cfg(feature = "hash")]
+pub mod hash;
 pub mod mixer;
+#[cfg(feature = "calculator")]
+pub mod offer;
+#[cfg(any(feature = "owned", feature = "serde"))]
+pub mod owned;
+#[cfg(feature = "prettier")]
 pub mod prettier;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(feature = "type-id")]
+pub mod type_id;
+pub mod typed;
+#[cfg(feature = "update")]
+pub mod update;
 pub mod util;
+#[cfg(feature = "verify")]
+pub mod verify;
 
 mod schemas;
+#[cfg(test)]
+mod test_util;
 
+pub use error::DasTypesError;
 pub use molecule::{error::VerificationError, prelude};
 pub use schemas::packed;
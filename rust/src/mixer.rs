@@ -1,212 +1,606 @@
-use super::schemas::packed::*;
-use alloc::{boxed::Box, string::ToString};
-use molecule::error::{VerificationError, VerificationResult};
-
-macro_rules! gen_trait_common_fns {
-    ({$( $fn_name:ident -> $fn_return:ty ),+}) => {
-        $(fn $fn_name(&self) -> $fn_return;)+
-    };
-}
-
-macro_rules! gen_trait_field_fns {
-    ({$( $field:ident -> $field_type:ty ),+}) => {
-        $(fn $field(&self) -> $field_type;)+
+#[cfg(feature = "diff")]
+use super::diff::{diff_field, push_field_change, ChangeKind, Diff, FieldChange, FieldNames};
+use super::{constants::DataType, error::DasTypesError, schemas::packed::*};
+use alloc::boxed::Box;
+#[cfg(feature = "diff")]
+use alloc::{string::String, vec::Vec};
+use molecule::prelude::*;
+
+/// Generate the mixer traits of one table and implement them for every version of the table.
+///
+/// The versions is a list of `version => Entity, Reader, try_into_fn { optional_field -> ReaderType }`, the
+/// try_into_fn of the last version should always be try_into_latest. The fields is a list of `field -> ReaderType`,
+/// they must exist in all versions of the table. The optional fields exist in some versions only, they return None for
+/// the versions which do not list them.
+macro_rules! gen_mixer {
+    ($entity_mixer:ident, $reader_mixer:ident, $from_slice:ident, $versions:tt, $fields:tt, $optional_fields:tt) => {
+        gen_mixer!(
+            @expand $entity_mixer, $reader_mixer, $from_slice, $versions, $versions, $fields, $fields, $optional_fields
+        );
     };
-}
+    (@expand $entity_mixer:ident, $reader_mixer:ident, $from_slice:ident, $all_versions:tt,
+        {$(
+            $version:literal => $entity:ident, $reader:ident, $try_into:ident
+                $({ $( $version_field:ident -> $version_field_type:ident ),* })?
+        );+},
+        $all_fields:tt,
+        {$( $field:ident -> $field_type:ident ),+},
+        {$( $optional_field:ident -> $optional_field_type:ident ),*}
+    ) => {
+        pub trait $entity_mixer {
+            fn version(&self) -> u32;
+
+            fn as_reader(&self) -> Box<dyn $reader_mixer<'_> + '_>;
+        }
+
+        pub trait $reader_mixer<'r> {
+            fn version(&self) -> u32;
+
+            $(fn $try_into(&self) -> Result<$reader<'r>, DasTypesError>;)+
+
+            $(fn $field(&self) -> $field_type<'r>;)+
+
+            $(
+                fn $optional_field(&self) -> Option<$optional_field_type<'r>> {
+                    None
+                }
+            )*
+        }
 
-macro_rules! gen_impl_field_fns {
-    ({$( $field:ident -> $field_type:ty ),+}) => {
         $(
-            fn $field(&self) -> $field_type {
-                self.$field()
+            impl $entity_mixer for $entity {
+                fn version(&self) -> u32 {
+                    $version
+                }
+
+                fn as_reader(&self) -> Box<dyn $reader_mixer<'_> + '_> {
+                    Box::new(self.as_reader())
+                }
             }
-        )+
-    };
-}
-
-pub trait AccountCellDataMixer {
-    gen_trait_common_fns!({
-        version -> u32,
-        as_reader -> Box<dyn AccountCellDataReaderMixer + '_>
-    });
-}
-
-impl AccountCellDataMixer for AccountCellDataV2 {
-    fn version(&self) -> u32 {
-        2
-    }
-
-    fn as_reader(&self) -> Box<dyn AccountCellDataReaderMixer + '_> {
-        Box::new(self.as_reader())
-    }
-}
-
-impl AccountCellDataMixer for AccountCellData {
-    fn version(&self) -> u32 {
-        3
-    }
-
-    fn as_reader(&self) -> Box<dyn AccountCellDataReaderMixer + '_> {
-        Box::new(self.as_reader())
-    }
-}
-
-pub trait AccountCellDataReaderMixer<'r> {
-    gen_trait_common_fns!({
-        version -> u32,
-        try_into_v2 -> VerificationResult<AccountCellDataV2Reader<'r>>,
-        try_into_latest -> VerificationResult<AccountCellDataReader<'r>>
-    });
-
-    gen_trait_field_fns!({
-        id -> AccountIdReader<'r>,
-        account -> AccountCharsReader<'r>,
-        registered_at -> Uint64Reader<'r>,
-        last_transfer_account_at -> Uint64Reader<'r>,
-        last_edit_manager_at -> Uint64Reader<'r>,
-        last_edit_records_at -> Uint64Reader<'r>,
-        status -> Uint8Reader<'r>,
-        records -> RecordsReader<'r>
-    });
-}
-
-impl<'r> AccountCellDataReaderMixer<'r> for AccountCellDataV2Reader<'r> {
-    fn version(&self) -> u32 {
-        2
-    }
-
-    fn try_into_v2(&self) -> VerificationResult<AccountCellDataV2Reader<'r>> {
-        AccountCellDataV2Reader::from_slice(self.as_slice())
-    }
-
-    fn try_into_latest(&self) -> VerificationResult<AccountCellDataReader<'r>> {
-        Err(VerificationError::OffsetsNotMatch("AccountCellDataReader".to_string()))
-    }
-
-    gen_impl_field_fns!({
-        id -> AccountIdReader<'r>,
-        account -> AccountCharsReader<'r>,
-        registered_at -> Uint64Reader<'r>,
-        last_transfer_account_at -> Uint64Reader<'r>,
-        last_edit_manager_at -> Uint64Reader<'r>,
-        last_edit_records_at -> Uint64Reader<'r>,
-        status -> Uint8Reader<'r>,
-        records -> RecordsReader<'r>
-    });
-}
-
-impl<'r> AccountCellDataReaderMixer<'r> for AccountCellDataReader<'r> {
-    fn version(&self) -> u32 {
-        3
-    }
-
-    fn try_into_v2(&self) -> VerificationResult<AccountCellDataV2Reader<'r>> {
-        Err(VerificationError::OffsetsNotMatch(
-            "AccountCellDataV2Reader".to_string(),
-        ))
-    }
-
-    fn try_into_latest(&self) -> VerificationResult<AccountCellDataReader<'r>> {
-        AccountCellDataReader::from_slice(self.as_slice())
-    }
 
-    gen_impl_field_fns!({
-        id -> AccountIdReader<'r>,
-        account -> AccountCharsReader<'r>,
-        registered_at -> Uint64Reader<'r>,
-        last_transfer_account_at -> Uint64Reader<'r>,
-        last_edit_manager_at -> Uint64Reader<'r>,
-        last_edit_records_at -> Uint64Reader<'r>,
-        status -> Uint8Reader<'r>,
-        records -> RecordsReader<'r>
-    });
-}
+            gen_mixer!(
+                @impl_reader $reader_mixer, $version, $reader, $all_versions, $all_fields,
+                {$($( $version_field -> $version_field_type ),*)?}
+            );
+        )+
 
-pub trait AccountSaleCellDataMixer {
-    gen_trait_common_fns!({
-        version -> u32,
-        as_reader -> Box<dyn AccountSaleCellDataReaderMixer + '_>
-    });
-}
+        /// Compare the mixers with all fields if they are the same version.
+        ///
+        /// Otherwise the change of version is reported as `version`, the fields of all versions and the optional
+        /// fields are compared, and every other field is reported as added, removed or modified without comparing, so
+        /// an upgrade can never hide a change in the fields it adds.
+        #[cfg(feature = "diff")]
+        impl<'a, 'b> Diff<dyn $reader_mixer<'b> + 'b> for dyn $reader_mixer<'a> + 'a {
+            fn diff_at(&self, other: &(dyn $reader_mixer<'b> + 'b), path: &mut String, changes: &mut Vec<FieldChange>) {
+                fn field_names(version: u32) -> &'static [&'static str] {
+                    match version {
+                        $($version => <$reader<'static> as FieldNames>::FIELD_NAMES,)+
+                        _ => &[],
+                    }
+                }
+
+                $(
+                    if let (Ok(old), Ok(new)) = (self.$try_into(), other.$try_into()) {
+                        return old.diff_at(&new, path, changes);
+                    }
+                )+
+
+                if self.version() != other.version() {
+                    push_field_change("version", ChangeKind::Modified, path, changes);
+                }
+                $(diff_field(stringify!($field), &self.$field(), &other.$field(), path, changes);)+
+                $(
+                    match (self.$optional_field(), other.$optional_field()) {
+                        (Some(old), Some(new)) => diff_field(stringify!($optional_field), &old, &new, path, changes),
+                        (Some(_), None) => push_field_change(stringify!($optional_field), ChangeKind::Removed, path, changes),
+                        (None, Some(_)) => push_field_change(stringify!($optional_field), ChangeKind::Added, path, changes),
+                        (None, None) => {}
+                    }
+                )*
+
+                let compared_fields = [$( stringify!($field), )+ $( stringify!($optional_field) ),*];
+                let (old_fields, new_fields) = (field_names(self.version()), field_names(other.version()));
+                for name in old_fields.iter().filter(|name| !compared_fields.contains(name)) {
+                    let kind = if new_fields.contains(name) {
+                        ChangeKind::Modified
+                    } else {
+                        ChangeKind::Removed
+                    };
+                    push_field_change(name, kind, path, changes);
+                }
+                for name in new_fields
+                    .iter()
+                    .filter(|name| !compared_fields.contains(name) && !old_fields.contains(name))
+                {
+                    push_field_change(name, ChangeKind::Added, path, changes);
+                }
+            }
+        }
 
-impl AccountSaleCellDataMixer for AccountSaleCellDataV1 {
-    fn version(&self) -> u32 {
-        1
-    }
+        /// Verify the slice as the reader of the given version and wrap it as a mixer.
+        pub fn $from_slice<'r>(version: u32, slice: &'r [u8]) -> Result<Box<dyn $reader_mixer<'r> + 'r>, DasTypesError> {
+            match version {
+                $($version => Ok(Box::new($reader::from_slice(slice)?)),)+
+                _ => Err(DasTypesError::UnknownVersion(stringify!($entity_mixer), version)),
+            }
+        }
+    };
+    (@impl_reader $reader_mixer:ident, $self_version:literal, $self_reader:ident,
+        {$( $version:literal => $entity:ident, $reader:ident, $try_into:ident $({ $( $tt:tt )* })? );+},
+        {$( $field:ident -> $field_type:ident ),+},
+        {$( $version_field:ident -> $version_field_type:ident ),*}
+    ) => {
+        impl<'r> $reader_mixer<'r> for $self_reader<'r> {
+            fn version(&self) -> u32 {
+                $self_version
+            }
 
-    fn as_reader(&self) -> Box<dyn AccountSaleCellDataReaderMixer + '_> {
-        Box::new(self.as_reader())
-    }
+            $(
+                fn $try_into(&self) -> Result<$reader<'r>, DasTypesError> {
+                    if $self_version == $version {
+                        Ok($reader::from_slice(self.as_slice())?)
+                    } else {
+                        Err(DasTypesError::VersionNotMatch($reader::NAME, $version, $self_version))
+                    }
+                }
+            )+
+
+            $(
+                fn $field(&self) -> $field_type<'r> {
+                    self.$field()
+                }
+            )+
+
+            $(
+                fn $version_field(&self) -> Option<$version_field_type<'r>> {
+                    Some(self.$version_field())
+                }
+            )*
+        }
+    };
+    (@versions {$( $version:literal => $entity:ident, $reader:ident, $try_into:ident $({ $( $tt:tt )* })? );+}) => {
+        &[$( $version ),+]
+    };
+    (@verify $entity_mixer:ident,
+        {$( $version:literal => $entity:ident, $reader:ident, $try_into:ident $({ $( $tt:tt )* })? );+},
+        $version_var:ident, $slice_var:ident
+    ) => {
+        match $version_var {
+            $($version => Ok($reader::verify($slice_var, false)?),)+
+            _ => Err(DasTypesError::UnknownVersion(stringify!($entity_mixer), $version_var)),
+        }
+    };
 }
 
-impl AccountSaleCellDataMixer for AccountSaleCellData {
-    fn version(&self) -> u32 {
-        2
-    }
+/// The registry of all versioned tables, it maps (DataType, version) to the reader of the table.
+///
+/// ⚠️ When a new version of a table is added, append it to the versions of the table here, then all mixers and the
+/// registry functions will be updated together.
+///
+/// ActionData and the configs are not wrapped in DataEntity, so they have no version on chain. They are registered as
+/// version 1 which is the only layout they have so far, callers should look it up with `latest_version_of` instead of
+/// reading a version from the witness.
+macro_rules! gen_mixers {
+    ($(
+        $data_type:ident => $entity_mixer:ident, $reader_mixer:ident, $from_slice:ident {
+            versions: $versions:tt,
+            fields: $fields:tt
+            $(, optional_fields: {$( $optional_field:ident -> $optional_field_type:ident ),*})?
+        }
+    ),+) => {
+        $(gen_mixer!(
+            $entity_mixer, $reader_mixer, $from_slice, $versions, $fields,
+            {$($( $optional_field -> $optional_field_type ),*)?}
+        );)+
+
+        /// Return all versions of the table which stored with the DataType, the last one is the latest.
+        pub fn versions_of(data_type: DataType) -> &'static [u32] {
+            match data_type {
+                $(DataType::$data_type => gen_mixer!(@versions $versions),)+
+                _ => &[],
+            }
+        }
 
-    fn as_reader(&self) -> Box<dyn AccountSaleCellDataReaderMixer + '_> {
-        Box::new(self.as_reader())
-    }
+        /// Verify the entity with the reader registered for the DataType and version.
+        pub fn verify_entity(data_type: DataType, version: u32, entity: &[u8]) -> Result<(), DasTypesError> {
+            match data_type {
+                $(DataType::$data_type => gen_mixer!(@verify $entity_mixer, $versions, version, entity),)+
+                _ => Err(DasTypesError::Unregistered(data_type)),
+            }
+        }
+    };
 }
 
-pub trait AccountSaleCellDataReaderMixer<'r> {
-    gen_trait_common_fns!({
-        version -> u32,
-        try_into_v1 -> VerificationResult<AccountSaleCellDataV1Reader<'r>>,
-        try_into_latest -> VerificationResult<AccountSaleCellDataReader<'r>>
-    });
-
-    gen_trait_field_fns!({
-        account_id -> AccountIdReader<'r>,
-        account -> BytesReader<'r>,
-        price -> Uint64Reader<'r>,
-        description -> BytesReader<'r>,
-        started_at -> Uint64Reader<'r>
-    });
+/// Return the latest version of the table which stored with the DataType.
+pub fn latest_version_of(data_type: DataType) -> Option<u32> {
+    versions_of(data_type).last().copied()
 }
 
-impl<'r> AccountSaleCellDataReaderMixer<'r> for AccountSaleCellDataV1Reader<'r> {
-    fn version(&self) -> u32 {
-        1
+gen_mixers!(
+    ActionData => ActionDataMixer, ActionDataReaderMixer, action_data_reader_mixer {
+        versions: {
+            1 => ActionData, ActionDataReader, try_into_latest
+        },
+        fields: {
+            action -> BytesReader,
+            params -> BytesReader
+        }
+    },
+    AccountCellData => AccountCellDataMixer, AccountCellDataReaderMixer, account_cell_data_reader_mixer {
+        versions: {
+            1 => AccountCellDataV1, AccountCellDataV1Reader, try_into_v1;
+            2 => AccountCellDataV2, AccountCellDataV2Reader, try_into_v2 {
+                last_transfer_account_at -> Uint64Reader,
+                last_edit_manager_at -> Uint64Reader,
+                last_edit_records_at -> Uint64Reader
+            };
+            3 => AccountCellData, AccountCellDataReader, try_into_latest {
+                last_transfer_account_at -> Uint64Reader,
+                last_edit_manager_at -> Uint64Reader,
+                last_edit_records_at -> Uint64Reader
+            }
+        },
+        fields: {
+            id -> AccountIdReader,
+            account -> AccountCharsReader,
+            registered_at -> Uint64Reader,
+            status -> Uint8Reader,
+            records -> RecordsReader
+        },
+        optional_fields: {
+            last_transfer_account_at -> Uint64Reader,
+            last_edit_manager_at -> Uint64Reader,
+            last_edit_records_at -> Uint64Reader
+        }
+    },
+    AccountSaleCellData => AccountSaleCellDataMixer, AccountSaleCellDataReaderMixer, account_sale_cell_data_reader_mixer {
+        versions: {
+            1 => AccountSaleCellDataV1, AccountSaleCellDataV1Reader, try_into_v1;
+            2 => AccountSaleCellData, AccountSaleCellDataReader, try_into_latest
+        },
+        fields: {
+            account_id -> AccountIdReader,
+            account -> BytesReader,
+            price -> Uint64Reader,
+            description -> BytesReader,
+            started_at -> Uint64Reader
+        }
+    },
+    AccountAuctionCellData => AccountAuctionCellDataMixer, AccountAuctionCellDataReaderMixer, account_auction_cell_data_reader_mixer {
+        versions: {
+            1 => AccountAuctionCellData, AccountAuctionCellDataReader, try_into_latest
+        },
+        fields: {
+            account_id -> AccountIdReader,
+            account -> BytesReader,
+            description -> BytesReader,
+            opening_price -> Uint64Reader,
+            increment_rate_each_bid -> Uint32Reader,
+            started_at -> Uint64Reader,
+            ended_at -> Uint64Reader,
+            current_bidder_lock -> ScriptReader,
+            current_bid_price -> Uint64Reader,
+            prev_bidder_profit_rate -> Uint32Reader
+        }
+    },
+    ProposalCellData => ProposalCellDataMixer, ProposalCellDataReaderMixer, proposal_cell_data_reader_mixer {
+        versions: {
+            1 => ProposalCellData, ProposalCellDataReader, try_into_latest
+        },
+        fields: {
+            proposer_lock -> ScriptReader,
+            created_at_height -> Uint64Reader,
+            slices -> SliceListReader
+        }
+    },
+    PreAccountCellData => PreAccountCellDataMixer, PreAccountCellDataReaderMixer, pre_account_cell_data_reader_mixer {
+        versions: {
+            1 => PreAccountCellData, PreAccountCellDataReader, try_into_latest
+        },
+        fields: {
+            account -> AccountCharsReader,
+            refund_lock -> ScriptReader,
+            owner_lock_args -> BytesReader,
+            inviter_id -> BytesReader,
+            inviter_lock -> ScriptOptReader,
+            channel_lock -> ScriptOptReader,
+            price -> PriceConfigReader,
+            quote -> Uint64Reader,
+            invited_discount -> Uint32Reader,
+            created_at -> Uint64Reader
+        }
+    },
+    IncomeCellData => IncomeCellDataMixer, IncomeCellDataReaderMixer, income_cell_data_reader_mixer {
+        versions: {
+            1 => IncomeCellData, IncomeCellDataReader, try_into_latest
+        },
+        fields: {
+            creator -> ScriptReader,
+            records -> IncomeRecordsReader
+        }
+    },
+    OfferCellData => OfferCellDataMixer, OfferCellDataReaderMixer, offer_cell_data_reader_mixer {
+        versions: {
+            1 => OfferCellData, OfferCellDataReader, try_into_latest
+        },
+        fields: {
+            account -> BytesReader,
+            price -> Uint64Reader,
+            message -> BytesReader,
+            inviter_lock -> ScriptReader,
+            channel_lock -> ScriptReader
+        }
+    },
+    SubAccount => SubAccountMixer, SubAccountReaderMixer, sub_account_reader_mixer {
+        versions: {
+            1 => SubAccount, SubAccountReader, try_into_latest
+        },
+        fields: {
+            lock -> ScriptReader,
+            id -> AccountIdReader,
+            account -> AccountCharsReader,
+            suffix -> BytesReader,
+            registered_at -> Uint64Reader,
+            expired_at -> Uint64Reader,
+            status -> Uint8Reader,
+            records -> RecordsReader,
+            nonce -> Uint64Reader,
+            enable_sub_account -> Uint8Reader,
+            renew_sub_account_price -> Uint64Reader
+        }
+    },
+    ConfigCellAccount => ConfigCellAccountMixer, ConfigCellAccountReaderMixer, config_cell_account_reader_mixer {
+        versions: {
+            1 => ConfigCellAccount, ConfigCellAccountReader, try_into_latest
+        },
+        fields: {
+            max_length -> Uint32Reader,
+            basic_capacity -> Uint64Reader,
+            prepared_fee_capacity -> Uint64Reader,
+            expiration_grace_period -> Uint32Reader,
+            record_min_ttl -> Uint32Reader,
+            record_size_limit -> Uint32Reader,
+            transfer_account_fee -> Uint64Reader,
+            edit_manager_fee -> Uint64Reader,
+            edit_records_fee -> Uint64Reader,
+            common_fee -> Uint64Reader,
+            transfer_account_throttle -> Uint32Reader,
+            edit_manager_throttle -> Uint32Reader,
+            edit_records_throttle -> Uint32Reader,
+            common_throttle -> Uint32Reader
+        }
+    },
+    ConfigCellApply => ConfigCellApplyMixer, ConfigCellApplyReaderMixer, config_cell_apply_reader_mixer {
+        versions: {
+            1 => ConfigCellApply, ConfigCellApplyReader, try_into_latest
+        },
+        fields: {
+            apply_min_waiting_block_number -> Uint32Reader,
+            apply_max_waiting_block_number -> Uint32Reader
+        }
+    },
+    ConfigCellIncome => ConfigCellIncomeMixer, ConfigCellIncomeReaderMixer, config_cell_income_reader_mixer {
+        versions: {
+            1 => ConfigCellIncome, ConfigCellIncomeReader, try_into_latest
+        },
+        fields: {
+            basic_capacity -> Uint64Reader,
+            max_records -> Uint32Reader,
+            min_transfer_capacity -> Uint64Reader
+        }
+    },
+    ConfigCellMain => ConfigCellMainMixer, ConfigCellMainReaderMixer, config_cell_main_reader_mixer {
+        versions: {
+            1 => ConfigCellMain, ConfigCellMainReader, try_into_latest
+        },
+        fields: {
+            status -> Uint8Reader,
+            type_id_table -> TypeIdTableReader,
+            das_lock_out_point_table -> DasLockOutPointTableReader
+        }
+    },
+    ConfigCellPrice => ConfigCellPriceMixer, ConfigCellPriceReaderMixer, config_cell_price_reader_mixer {
+        versions: {
+            1 => ConfigCellPrice, ConfigCellPriceReader, try_into_latest
+        },
+        fields: {
+            discount -> DiscountConfigReader,
+            prices -> PriceConfigListReader
+        }
+    },
+    ConfigCellProposal => ConfigCellProposalMixer, ConfigCellProposalReaderMixer, config_cell_proposal_reader_mixer {
+        versions: {
+            1 => ConfigCellProposal, ConfigCellProposalReader, try_into_latest
+        },
+        fields: {
+            proposal_min_confirm_interval -> Uint8Reader,
+            proposal_min_extend_interval -> Uint8Reader,
+            proposal_min_recycle_interval -> Uint8Reader,
+            proposal_max_account_affect -> Uint32Reader,
+            proposal_max_pre_account_contain -> Uint32Reader
+        }
+    },
+    ConfigCellProfitRate => ConfigCellProfitRateMixer, ConfigCellProfitRateReaderMixer, config_cell_profit_rate_reader_mixer {
+        versions: {
+            1 => ConfigCellProfitRate, ConfigCellProfitRateReader, try_into_latest
+        },
+        fields: {
+            inviter -> Uint32Reader,
+            channel -> Uint32Reader,
+            proposal_create -> Uint32Reader,
+            proposal_confirm -> Uint32Reader,
+            income_consolidate -> Uint32Reader,
+            sale_buyer_inviter -> Uint32Reader,
+            sale_buyer_channel -> Uint32Reader,
+            sale_das -> Uint32Reader,
+            auction_bidder_inviter -> Uint32Reader,
+            auction_bidder_channel -> Uint32Reader,
+            auction_das -> Uint32Reader,
+            auction_prev_bidder -> Uint32Reader
+        }
+    },
+    ConfigCellRelease => ConfigCellReleaseMixer, ConfigCellReleaseReaderMixer, config_cell_release_reader_mixer {
+        versions: {
+            1 => ConfigCellRelease, ConfigCellReleaseReader, try_into_latest
+        },
+        fields: {
+            lucky_number -> Uint32Reader
+        }
+    },
+    ConfigCellSecondaryMarket => ConfigCellSecondaryMarketMixer, ConfigCellSecondaryMarketReaderMixer, config_cell_secondary_market_reader_mixer {
+        versions: {
+            1 => ConfigCellSecondaryMarket, ConfigCellSecondaryMarketReader, try_into_latest
+        },
+        fields: {
+            common_fee -> Uint64Reader,
+            sale_min_price -> Uint64Reader,
+            sale_expiration_limit -> Uint32Reader,
+            sale_description_bytes_limit -> Uint32Reader,
+            sale_cell_basic_capacity -> Uint64Reader,
+            sale_cell_prepared_fee_capacity -> Uint64Reader,
+            auction_max_extendable_duration -> Uint32Reader,
+            auction_duration_increment_each_bid -> Uint32Reader,
+            auction_min_opening_price -> Uint64Reader,
+            auction_min_increment_rate_each_bid -> Uint32Reader,
+            auction_description_bytes_limit -> Uint32Reader,
+            auction_cell_basic_capacity -> Uint64Reader,
+            auction_cell_prepared_fee_capacity -> Uint64Reader,
+            offer_min_price -> Uint64Reader,
+            offer_cell_basic_capacity -> Uint64Reader,
+            offer_cell_prepared_fee_capacity -> Uint64Reader,
+            offer_message_bytes_limit -> Uint32Reader
+        }
+    },
+    ConfigCellReverseResolution => ConfigCellReverseResolutionMixer, ConfigCellReverseResolutionReaderMixer, config_cell_reverse_resolution_reader_mixer {
+        versions: {
+            1 => ConfigCellReverseResolution, ConfigCellReverseResolutionReader, try_into_latest
+        },
+        fields: {
+            record_basic_capacity -> Uint64Reader,
+            record_prepared_fee_capacity -> Uint64Reader,
+            common_fee -> Uint64Reader
+        }
+    },
+    ConfigCellSubAccount => ConfigCellSubAccountMixer, ConfigCellSubAccountReaderMixer, config_cell_sub_account_reader_mixer {
+        versions: {
+            1 => ConfigCellSubAccount, ConfigCellSubAccountReader, try_into_latest
+        },
+        fields: {
+            basic_capacity -> Uint64Reader,
+            prepared_fee_capacity -> Uint64Reader,
+            new_sub_account_price -> Uint64Reader,
+            renew_sub_account_price -> Uint64Reader,
+            common_fee -> Uint64Reader,
+            create_fee -> Uint64Reader,
+            edit_fee -> Uint64Reader,
+            renew_fee -> Uint64Reader,
+            recycle_fee -> Uint64Reader
+        }
     }
-
-    fn try_into_v1(&self) -> VerificationResult<AccountSaleCellDataV1Reader<'r>> {
-        AccountSaleCellDataV1Reader::from_slice(self.as_slice())
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_cell_data_v1() -> AccountCellDataV1 {
+        AccountCellDataV1::new_builder()
+            .registered_at(Uint64::from(1u64))
+            .updated_at(Uint64::from(2u64))
+            .status(Uint8::from(1u8))
+            .build()
     }
 
-    fn try_into_latest(&self) -> VerificationResult<AccountSaleCellDataReader<'r>> {
-        Err(VerificationError::OffsetsNotMatch("AccountCellDataReader".to_string()))
+    #[test]
+    fn versions_of_tables() {
+        assert_eq!(versions_of(DataType::AccountCellData), &[1, 2, 3]);
+        assert_eq!(versions_of(DataType::AccountSaleCellData), &[1, 2]);
+        assert_eq!(versions_of(DataType::SubAccount), &[1]);
+        assert_eq!(versions_of(DataType::ConfigCellMain), &[1]);
+        assert!(versions_of(DataType::ConfigCellCharSetEn).is_empty());
+
+        assert_eq!(latest_version_of(DataType::AccountCellData), Some(3));
+        assert_eq!(latest_version_of(DataType::ActionData), Some(1));
+        assert_eq!(latest_version_of(DataType::ConfigCellPreservedAccount00), None);
     }
 
-    gen_impl_field_fns!({
-        account_id -> AccountIdReader<'r>,
-        account -> BytesReader<'r>,
-        price -> Uint64Reader<'r>,
-        description -> BytesReader<'r>,
-        started_at -> Uint64Reader<'r>
-    });
-}
-
-impl<'r> AccountSaleCellDataReaderMixer<'r> for AccountSaleCellDataReader<'r> {
-    fn version(&self) -> u32 {
-        2
+    #[test]
+    fn verify_entity_of_each_version() {
+        let v1 = account_cell_data_v1();
+        let v2 = AccountCellDataV2::default();
+        let v3 = AccountCellData::default();
+        assert!(verify_entity(DataType::AccountCellData, 1, v1.as_slice()).is_ok());
+        assert!(verify_entity(DataType::AccountCellData, 2, v2.as_slice()).is_ok());
+        assert!(verify_entity(DataType::AccountCellData, 3, v3.as_slice()).is_ok());
+
+        match verify_entity(DataType::AccountCellData, 3, v2.as_slice()) {
+            Err(DasTypesError::Verification(_)) => {}
+            other => panic!("{:?}", other),
+        }
+        match verify_entity(DataType::AccountCellData, 4, v3.as_slice()) {
+            Err(DasTypesError::UnknownVersion(name, 4)) => assert_eq!(name, "AccountCellDataMixer"),
+            other => panic!("{:?}", other),
+        }
+        match verify_entity(DataType::ConfigCellMain, 2, ConfigCellMain::default().as_slice()) {
+            Err(DasTypesError::UnknownVersion(name, 2)) => assert_eq!(name, "ConfigCellMainMixer"),
+            other => panic!("{:?}", other),
+        }
+        match verify_entity(DataType::ConfigCellCharSetEn, 1, &[]) {
+            Err(DasTypesError::Unregistered(DataType::ConfigCellCharSetEn)) => {}
+            other => panic!("{:?}", other),
+        }
     }
 
-    fn try_into_v1(&self) -> VerificationResult<AccountSaleCellDataV1Reader<'r>> {
-        Err(VerificationError::OffsetsNotMatch(
-            "AccountCellDataV2Reader".to_string(),
-        ))
+    #[test]
+    fn reader_mixers_of_each_version() {
+        let v1 = account_cell_data_v1();
+        let mixer = account_cell_data_reader_mixer(1, v1.as_slice()).unwrap();
+        assert_eq!(mixer.version(), 1);
+        assert!(mixer.try_into_v1().is_ok());
+        assert!(mixer.try_into_v2().is_err());
+        assert_eq!(u64::from(mixer.registered_at()), 1);
+        assert_eq!(u8::from(mixer.status()), 1);
+        assert!(mixer.last_edit_records_at().is_none());
+
+        let v2 = AccountCellDataV2::new_builder()
+            .last_edit_records_at(Uint64::from(5u64))
+            .build();
+        let mixer = account_cell_data_reader_mixer(2, v2.as_slice()).unwrap();
+        assert_eq!(mixer.version(), 2);
+        assert_eq!(mixer.try_into_v2().unwrap().as_slice(), v2.as_slice());
+        match mixer.try_into_latest() {
+            Err(DasTypesError::VersionNotMatch(name, 3, 2)) => assert_eq!(name, "AccountCellDataReader"),
+            other => panic!("{:?}", other.map(|v| v.as_slice().len())),
+        }
+        assert_eq!(mixer.last_edit_records_at().map(u64::from), Some(5));
+
+        let v3 = AccountCellData::default();
+        let mixer = account_cell_data_reader_mixer(3, v3.as_slice()).unwrap();
+        assert!(mixer.try_into_latest().is_ok());
+        assert_eq!(mixer.last_transfer_account_at().map(u64::from), Some(0));
+        assert_eq!(AccountCellDataMixer::as_reader(&v3).version(), 3);
+        assert_eq!(AccountCellDataMixer::version(&v1), 1);
+
+        // The slice must be in the layout of the version.
+        assert!(account_cell_data_reader_mixer(1, v3.as_slice()).is_err());
+        assert!(account_cell_data_reader_mixer(3, v1.as_slice()).is_err());
+        match account_cell_data_reader_mixer(0, v3.as_slice()) {
+            Err(DasTypesError::UnknownVersion(name, 0)) => assert_eq!(name, "AccountCellDataMixer"),
+            Err(other) => panic!("{:?}", other),
+            Ok(_) => panic!("version 0 should be unknown"),
+        };
     }
 
-    fn try_into_latest(&self) -> VerificationResult<AccountSaleCellDataReader<'r>> {
-        AccountSaleCellDataReader::from_slice(self.as_slice())
+    #[test]
+    fn reader_mixers_of_unversioned_tables() {
+        let config = ConfigCellMain::default();
+        let mixer = config_cell_main_reader_mixer(1, config.as_slice()).unwrap();
+        assert_eq!(mixer.version(), 1);
+        assert_eq!(mixer.try_into_latest().unwrap().as_slice(), config.as_slice());
+        assert!(config_cell_main_reader_mixer(1, AccountCellData::default().as_slice()).is_err());
+
+        let offer = OfferCellData::new_builder().price(Uint64::from(7u64)).build();
+        let mixer = offer_cell_data_reader_mixer(1, offer.as_slice()).unwrap();
+        assert_eq!(u64::from(mixer.price()), 7);
     }
-
-    gen_impl_field_fns!({
-        account_id -> AccountIdReader<'r>,
-        account -> BytesReader<'r>,
-        price -> Uint64Reader<'r>,
-        description -> BytesReader<'r>,
-        started_at -> Uint64Reader<'r>
-    });
 }
@@ -0,0 +1,311 @@
+use super::{constants::RATE_BASE, error::DasTypesError, schemas::packed::*};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum OfferError {
+    /// The price is lower than offer_min_price.
+    PriceTooLow,
+    /// The message is longer than offer_message_bytes_limit.
+    MessageTooLong,
+    /// The capacity of the OfferCell can not cover the price or the basic capacity and prepared fee.
+    CapacityNotEnough,
+    /// The calculation of capacities or profits overflowed u64, or the profits are more than the price.
+    Overflow,
+}
+
+impl From<OfferError> for DasTypesError {
+    fn from(err: OfferError) -> Self {
+        DasTypesError::Offer(err)
+    }
+}
+
+/// How the price of an offer is divided when the offer is accepted.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct OfferSettlement {
+    pub seller: u64,
+    pub inviter: u64,
+    pub channel: u64,
+    pub das: u64,
+}
+
+/// The minimum capacity of an OfferCell.
+///
+/// The capacity is both the payment of the offer and the storage of the cell, so it must be at least the price of the
+/// offer and at least the basic capacity with prepared fee, whichever is larger. They are not added up.
+pub fn offer_cell_required_capacity(
+    offer: OfferCellDataReader,
+    config: ConfigCellSecondaryMarketReader,
+) -> Result<u64, OfferError> {
+    let price = u64::from(offer.price());
+    let basic_capacity = u64::from(config.offer_cell_basic_capacity())
+        .checked_add(u64::from(config.offer_cell_prepared_fee_capacity()))
+        .ok_or(OfferError::Overflow)?;
+
+    Ok(price.max(basic_capacity))
+}
+
+/// Verify the OfferCellData and the capacity of the OfferCell with ConfigCellSecondaryMarket.
+pub fn verify_offer(
+    offer: OfferCellDataReader,
+    config: ConfigCellSecondaryMarketReader,
+    capacity: u64,
+) -> Result<(), OfferError> {
+    if u64::from(offer.price()) < u64::from(config.offer_min_price()) {
+        return Err(OfferError::PriceTooLow);
+    }
+    if offer.message().raw_data().len() > u32::from(config.offer_message_bytes_limit()) as usize {
+        return Err(OfferError::MessageTooLong);
+    }
+    if capacity < offer_cell_required_capacity(offer, config)? {
+        return Err(OfferError::CapacityNotEnough);
+    }
+
+    Ok(())
+}
+
+/// Divide the price of an offer when the seller accepts it.
+///
+/// The inviter and channel of the offer maker take their profits with the rates of secondary market, if any of them is
+/// not set, which means the code_hash of its lock is all zero, its profit belongs to DAS. The seller receives the rest.
+pub fn settle_accepted_offer(
+    offer: OfferCellDataReader,
+    profit_rate: ConfigCellProfitRateReader,
+) -> Result<OfferSettlement, OfferError> {
+    let price = u64::from(offer.price());
+    let profit_of = |rate: Uint32Reader| {
+        let profit = price as u128 * u32::from(rate) as u128 / RATE_BASE as u128;
+        if profit > u64::MAX as u128 {
+            return Err(OfferError::Overflow);
+        }
+        Ok(profit as u64)
+    };
+
+    let mut inviter = profit_of(profit_rate.sale_buyer_inviter())?;
+    let mut channel = profit_of(profit_rate.sale_buyer_channel())?;
+    let mut das = profit_of(profit_rate.sale_das())?;
+    if is_empty_lock(offer.inviter_lock()) {
+        das = das.checked_add(inviter).ok_or(OfferError::Overflow)?;
+        inviter = 0;
+    }
+    if is_empty_lock(offer.channel_lock()) {
+        das = das.checked_add(channel).ok_or(OfferError::Overflow)?;
+        channel = 0;
+    }
+
+    let seller = inviter
+        .checked_add(channel)
+        .and_then(|profits| profits.checked_add(das))
+        .and_then(|profits| price.checked_sub(profits))
+        .ok_or(OfferError::Overflow)?;
+
+    Ok(OfferSettlement {
+        seller,
+        inviter,
+        channel,
+        das,
+    })
+}
+
+fn is_empty_lock(lock: ScriptReader) -> bool {
+    lock.code_hash().raw_data().iter().all(|byte| *byte == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use molecule::prelude::*;
+
+    fn lock() -> Script {
+        Script::new_builder().code_hash(Hash::from([1u8; 32])).build()
+    }
+
+    fn offer(price: u64, inviter_lock: Script, channel_lock: Script) -> OfferCellData {
+        OfferCellData::new_builder()
+            .price(Uint64::from(price))
+            .message(Bytes::from(&b"hi"[..]))
+            .inviter_lock(inviter_lock)
+            .channel_lock(channel_lock)
+            .build()
+    }
+
+    fn config() -> ConfigCellSecondaryMarket {
+        ConfigCellSecondaryMarket::new_builder()
+            .offer_min_price(Uint64::from(100))
+            .offer_message_bytes_limit(Uint32::from(2))
+            .offer_cell_basic_capacity(Uint64::from(15000))
+            .offer_cell_prepared_fee_capacity(Uint64::from(5000))
+            .build()
+    }
+
+    fn profit_rate(inviter: u32, channel: u32, das: u32) -> ConfigCellProfitRate {
+        ConfigCellProfitRate::new_builder()
+            .sale_buyer_inviter(Uint32::from(inviter))
+            .sale_buyer_channel(Uint32::from(channel))
+            .sale_das(Uint32::from(das))
+            .build()
+    }
+
+    #[test]
+    fn required_capacity_is_the_larger_one() {
+        let config = config();
+
+        let data = offer(10000, lock(), lock());
+        assert_eq!(
+            offer_cell_required_capacity(data.as_reader(), config.as_reader()),
+            Ok(20000)
+        );
+
+        let data = offer(30000, lock(), lock());
+        assert_eq!(
+            offer_cell_required_capacity(data.as_reader(), config.as_reader()),
+            Ok(30000)
+        );
+
+        let config = config
+            .as_builder()
+            .offer_cell_prepared_fee_capacity(Uint64::from(u64::MAX))
+            .build();
+        assert_eq!(
+            offer_cell_required_capacity(data.as_reader(), config.as_reader()),
+            Err(OfferError::Overflow)
+        );
+    }
+
+    #[test]
+    fn errors_propagate_as_das_types_error() {
+        fn create_offer(
+            offer: OfferCellDataReader,
+            config: ConfigCellSecondaryMarketReader,
+            capacity: u64,
+        ) -> Result<u64, DasTypesError> {
+            verify_offer(offer, config, capacity)?;
+            Ok(offer_cell_required_capacity(offer, config)?)
+        }
+
+        let config = config();
+        let data = offer(10000, lock(), lock());
+        assert_eq!(
+            create_offer(data.as_reader(), config.as_reader(), 20000).unwrap(),
+            20000
+        );
+        match create_offer(data.as_reader(), config.as_reader(), 19999) {
+            Err(DasTypesError::Offer(OfferError::CapacityNotEnough)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn verify_offer_limits() {
+        let config = config();
+
+        let data = offer(10000, lock(), lock());
+        assert_eq!(
+            verify_offer(data.as_reader(), config.as_reader(), 19999),
+            Err(OfferError::CapacityNotEnough)
+        );
+        assert_eq!(verify_offer(data.as_reader(), config.as_reader(), 20000), Ok(()));
+
+        let data = offer(99, lock(), lock());
+        assert_eq!(
+            verify_offer(data.as_reader(), config.as_reader(), 20000),
+            Err(OfferError::PriceTooLow)
+        );
+
+        let data = offer(10000, lock(), lock())
+            .as_builder()
+            .message(Bytes::from(&b"hey"[..]))
+            .build();
+        assert_eq!(
+            verify_offer(data.as_reader(), config.as_reader(), 20000),
+            Err(OfferError::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn settle_with_inviter_and_channel() {
+        let data = offer(10000, lock(), lock());
+        let rate = profit_rate(100, 200, 300);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Ok(OfferSettlement {
+                seller: 9400,
+                inviter: 100,
+                channel: 200,
+                das: 300,
+            })
+        );
+    }
+
+    #[test]
+    fn settle_without_inviter_and_channel() {
+        let data = offer(10000, Script::default(), Script::default());
+        let rate = profit_rate(100, 200, 300);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Ok(OfferSettlement {
+                seller: 9400,
+                inviter: 0,
+                channel: 0,
+                das: 600,
+            })
+        );
+    }
+
+    #[test]
+    fn settle_rounds_profits_down() {
+        let data = offer(999, lock(), lock());
+        let rate = profit_rate(100, 100, 100);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Ok(OfferSettlement {
+                seller: 972,
+                inviter: 9,
+                channel: 9,
+                das: 9,
+            })
+        );
+    }
+
+    #[test]
+    fn settle_with_edge_rates() {
+        let data = offer(10000, lock(), lock());
+
+        let rate = profit_rate(0, 0, 0);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()).map(|s| s.seller),
+            Ok(10000)
+        );
+
+        let rate = profit_rate(0, 0, RATE_BASE as u32);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()).map(|s| (s.seller, s.das)),
+            Ok((0, 10000))
+        );
+    }
+
+    #[test]
+    fn settle_profits_more_than_price() {
+        let data = offer(10000, lock(), lock());
+        let rate = profit_rate(RATE_BASE as u32, 1, 0);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Err(OfferError::Overflow)
+        );
+    }
+
+    #[test]
+    fn settle_profits_overflow() {
+        let data = offer(u64::MAX, Script::default(), Script::default());
+
+        let rate = profit_rate(u32::MAX, 0, 0);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Err(OfferError::Overflow)
+        );
+
+        let rate = profit_rate(RATE_BASE as u32, 0, RATE_BASE as u32);
+        assert_eq!(
+            settle_accepted_offer(data.as_reader(), rate.as_reader()),
+            Err(OfferError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,627 @@
+use super::{
+    constants::{AccountStatus, CharSetType, ProposalSliceItemType, SubAccountEnableStatus, SystemStatus},
+    error::DasTypesError,
+    schemas::packed::*,
+};
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use molecule::{bytes, prelude::*};
+
+/// The conversion between an owned value and the molecule type E.
+///
+/// It is implemented for every pair of owned and molecule types, so the owned structs of tables can be generated
+/// field by field.
+pub(crate) trait OwnedField<E>: Sized {
+    /// Convert from the slice of a reader of E, the slice must have been verified.
+    fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError>;
+
+    fn to_entity(&self) -> E;
+}
+
+/// Convert from the slice of a reader of E, it fails if the owned value can not be converted back to the same bytes.
+///
+/// The owned structs only have the fields of the current schema, so the extra fields of tables decoded in compatible
+/// mode would be lost silently without this check.
+pub(crate) fn from_verified_exactly<E: Entity, T: OwnedField<E>>(slice: &[u8]) -> Result<T, DasTypesError> {
+    let owned = T::from_verified(slice)?;
+    if owned.to_entity().as_slice() != slice {
+        return Err(DasTypesError::LossyConversion(E::NAME));
+    }
+
+    Ok(owned)
+}
+
+macro_rules! impl_owned_uint {
+    ($( $entity:ident, $reader:ident => $owned:ty ),+) => {
+        $(
+            impl OwnedField<$entity> for $owned {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    Ok(<$owned>::from($reader::new_unchecked(slice)))
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::from(*self)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_owned_byte_array {
+    ($( $entity:ident, $length:literal ),+) => {
+        $(
+            impl OwnedField<$entity> for [u8; $length] {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    let mut ret = [0u8; $length];
+                    ret.copy_from_slice(slice);
+                    Ok(ret)
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::new_unchecked(bytes::Bytes::from(self.to_vec()))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_owned_enum {
+    ($( $enum_type:ident: $entity:ident, $reader:ident, $repr:ty ),+) => {
+        $(
+            impl OwnedField<$entity> for $enum_type {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    $enum_type::try_from($reader::new_unchecked(slice))
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::from(*self as $repr)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_owned_option {
+    ($( $entity:ident => $inner:ident, $inner_owned:ty ),+) => {
+        $(
+            impl OwnedField<$entity> for Option<$inner_owned> {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    if slice.is_empty() {
+                        Ok(None)
+                    } else {
+                        <$inner_owned as OwnedField<$inner>>::from_verified(slice).map(Some)
+                    }
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::new_builder()
+                        .set(self.as_ref().map(<$inner_owned as OwnedField<$inner>>::to_entity))
+                        .build()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_owned_vec {
+    ($( $entity:ident, $reader:ident => $item:ident, $item_owned:ty ),+) => {
+        $(
+            impl OwnedField<$entity> for Vec<$item_owned> {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    $reader::new_unchecked(slice)
+                        .iter()
+                        .map(|item| <$item_owned as OwnedField<$item>>::from_verified(item.as_slice()))
+                        .collect()
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::new_builder()
+                        .set(self.iter().map(<$item_owned as OwnedField<$item>>::to_entity).collect())
+                        .build()
+                }
+            }
+        )+
+    };
+}
+
+/// Generate the owned struct of a table or struct, with conversions both ways.
+///
+/// The conversion from the molecule type fails when a field holds a value which its owned type can not represent, like
+/// an unknown AccountStatus or an account which is not UTF-8, or when the table has extra fields of compatible mode.
+macro_rules! gen_owned {
+    ($( $entity:ident, $reader:ident => $owned:ident { $( $field:ident: $field_entity:ident => $field_owned:ty ),+ } );+) => {
+        $(
+            #[derive(Debug, PartialEq, Clone)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(deny_unknown_fields))]
+            pub struct $owned {
+                $(
+                    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::SerdeValue"))]
+                    pub $field: $field_owned,
+                )+
+            }
+
+            impl OwnedField<$entity> for $owned {
+                fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+                    let reader = $reader::new_unchecked(slice);
+                    Ok($owned {
+                        $($field: <$field_owned as OwnedField<$field_entity>>::from_verified(reader.$field().as_slice())?,)+
+                    })
+                }
+
+                fn to_entity(&self) -> $entity {
+                    $entity::new_builder()
+                        $(.$field(<$field_owned as OwnedField<$field_entity>>::to_entity(&self.$field)))+
+                        .build()
+                }
+            }
+
+            impl<'r> TryFrom<$reader<'r>> for $owned {
+                type Error = DasTypesError;
+
+                fn try_from(v: $reader<'r>) -> Result<Self, Self::Error> {
+                    from_verified_exactly::<$entity, $owned>(v.as_slice())
+                }
+            }
+
+            impl TryFrom<$entity> for $owned {
+                type Error = DasTypesError;
+
+                fn try_from(v: $entity) -> Result<Self, Self::Error> {
+                    Self::try_from(v.as_reader())
+                }
+            }
+
+            impl From<$owned> for $entity {
+                fn from(v: $owned) -> Self {
+                    <$owned as OwnedField<$entity>>::to_entity(&v)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl serde::Serialize for $entity {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let owned = $owned::try_from(self.as_reader()).map_err(serde::ser::Error::custom)?;
+                    owned.serialize(serializer)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> serde::Deserialize<'de> for $entity {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    $owned::deserialize(deserializer).map($entity::from)
+                }
+            }
+        )+
+    };
+}
+
+impl OwnedField<Byte> for u8 {
+    fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+        Ok(slice[0])
+    }
+
+    fn to_entity(&self) -> Byte {
+        Byte::new(*self)
+    }
+}
+
+impl OwnedField<Bytes> for Vec<u8> {
+    fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+        Ok(BytesReader::new_unchecked(slice).raw_data().to_vec())
+    }
+
+    fn to_entity(&self) -> Bytes {
+        Bytes::from(self.as_slice())
+    }
+}
+
+/// Accounts and chars are stored as Bytes, but they are always UTF-8.
+impl OwnedField<Bytes> for String {
+    fn from_verified(slice: &[u8]) -> Result<Self, DasTypesError> {
+        String::from_utf8(BytesReader::new_unchecked(slice).raw_data().to_vec())
+            .map_err(|_| DasTypesError::InvalidUtf8("Bytes"))
+    }
+
+    fn to_entity(&self) -> Bytes {
+        Bytes::from(self.as_bytes())
+    }
+}
+
+impl_owned_uint!(
+    Uint8, Uint8Reader => u8,
+    Uint32, Uint32Reader => u32,
+    Uint64, Uint64Reader => u64,
+    Timestamp, TimestampReader => u64
+);
+
+impl_owned_byte_array!(Hash, 32, AccountId, 20);
+
+impl_owned_enum!(
+    SystemStatus: Uint8, Uint8Reader, u8,
+    ProposalSliceItemType: Uint8, Uint8Reader, u8,
+    AccountStatus: Uint8, Uint8Reader, u8,
+    SubAccountEnableStatus: Uint8, Uint8Reader, u8,
+    CharSetType: Uint32, Uint32Reader, u32
+);
+
+impl_owned_option!(
+    HashOpt => Hash, [u8; 32],
+    ScriptOpt => Script, ScriptOwned,
+    DataEntityOpt => DataEntity, DataEntityOwned
+);
+
+impl_owned_vec!(
+    Chars, CharsReader => Bytes, Vec<u8>,
+    PriceConfigList, PriceConfigListReader => PriceConfig, PriceConfigOwned,
+    SliceList, SliceListReader => SL, Vec<ProposalItemOwned>,
+    SL, SLReader => ProposalItem, ProposalItemOwned,
+    IncomeRecords, IncomeRecordsReader => IncomeRecord, IncomeRecordOwned,
+    Records, RecordsReader => Record, RecordOwned,
+    AccountChars, AccountCharsReader => AccountChar, AccountCharOwned
+);
+
+gen_owned!(
+    OutPoint, OutPointReader => OutPointOwned {
+        tx_hash: Hash => [u8; 32],
+        index: Uint32 => u32
+    };
+    Script, ScriptReader => ScriptOwned {
+        code_hash: Hash => [u8; 32],
+        hash_type: Byte => u8,
+        args: Bytes => Vec<u8>
+    };
+    Data, DataReader => DataOwned {
+        dep: DataEntityOpt => Option<DataEntityOwned>,
+        old: DataEntityOpt => Option<DataEntityOwned>,
+        new: DataEntityOpt => Option<DataEntityOwned>
+    };
+    DataEntity, DataEntityReader => DataEntityOwned {
+        index: Uint32 => u32,
+        version: Uint32 => u32,
+        entity: Bytes => Vec<u8>
+    };
+    ActionData, ActionDataReader => ActionDataOwned {
+        action: Bytes => Vec<u8>,
+        params: Bytes => Vec<u8>
+    };
+    ConfigCellMain, ConfigCellMainReader => ConfigCellMainOwned {
+        status: Uint8 => SystemStatus,
+        type_id_table: TypeIdTable => TypeIdTableOwned,
+        das_lock_out_point_table: DasLockOutPointTable => DasLockOutPointTableOwned
+    };
+    TypeIdTable, TypeIdTableReader => TypeIdTableOwned {
+        account_cell: Hash => [u8; 32],
+        apply_register_cell: Hash => [u8; 32],
+        balance_cell: Hash => [u8; 32],
+        income_cell: Hash => [u8; 32],
+        pre_account_cell: Hash => [u8; 32],
+        proposal_cell: Hash => [u8; 32],
+        account_sale_cell: Hash => [u8; 32],
+        account_auction_cell: Hash => [u8; 32],
+        offer_cell: Hash => [u8; 32],
+        reverse_record_cell: Hash => [u8; 32],
+        sub_account_cell: Hash => [u8; 32]
+    };
+    DasLockOutPointTable, DasLockOutPointTableReader => DasLockOutPointTableOwned {
+        ckb_signall: OutPoint => OutPointOwned,
+        ckb_multisign: OutPoint => OutPointOwned,
+        ckb_anyone_can_pay: OutPoint => OutPointOwned,
+        eth: OutPoint => OutPointOwned,
+        tron: OutPoint => OutPointOwned,
+        ed25519: OutPoint => OutPointOwned
+    };
+    ConfigCellAccount, ConfigCellAccountReader => ConfigCellAccountOwned {
+        max_length: Uint32 => u32,
+        basic_capacity: Uint64 => u64,
+        prepared_fee_capacity: Uint64 => u64,
+        expiration_grace_period: Uint32 => u32,
+        record_min_ttl: Uint32 => u32,
+        record_size_limit: Uint32 => u32,
+        transfer_account_fee: Uint64 => u64,
+        edit_manager_fee: Uint64 => u64,
+        edit_records_fee: Uint64 => u64,
+        common_fee: Uint64 => u64,
+        transfer_account_throttle: Uint32 => u32,
+        edit_manager_throttle: Uint32 => u32,
+        edit_records_throttle: Uint32 => u32,
+        common_throttle: Uint32 => u32
+    };
+    ConfigCellApply, ConfigCellApplyReader => ConfigCellApplyOwned {
+        apply_min_waiting_block_number: Uint32 => u32,
+        apply_max_waiting_block_number: Uint32 => u32
+    };
+    ConfigCellPrice, ConfigCellPriceReader => ConfigCellPriceOwned {
+        discount: DiscountConfig => DiscountConfigOwned,
+        prices: PriceConfigList => Vec<PriceConfigOwned>
+    };
+    DiscountConfig, DiscountConfigReader => DiscountConfigOwned {
+        invited_discount: Uint32 => u32
+    };
+    PriceConfig, PriceConfigReader => PriceConfigOwned {
+        length: Uint8 => u8,
+        new: Uint64 => u64,
+        renew: Uint64 => u64
+    };
+    ConfigCellProposal, ConfigCellProposalReader => ConfigCellProposalOwned {
+        proposal_min_confirm_interval: Uint8 => u8,
+        proposal_min_extend_interval: Uint8 => u8,
+        proposal_min_recycle_interval: Uint8 => u8,
+        proposal_max_account_affect: Uint32 => u32,
+        proposal_max_pre_account_contain: Uint32 => u32
+    };
+    ConfigCellProfitRate, ConfigCellProfitRateReader => ConfigCellProfitRateOwned {
+        inviter: Uint32 => u32,
+        channel: Uint32 => u32,
+        proposal_create: Uint32 => u32,
+        proposal_confirm: Uint32 => u32,
+        income_consolidate: Uint32 => u32,
+        sale_buyer_inviter: Uint32 => u32,
+        sale_buyer_channel: Uint32 => u32,
+        sale_das: Uint32 => u32,
+        auction_bidder_inviter: Uint32 => u32,
+        auction_bidder_channel: Uint32 => u32,
+        auction_das: Uint32 => u32,
+        auction_prev_bidder: Uint32 => u32
+    };
+    ConfigCellIncome, ConfigCellIncomeReader => ConfigCellIncomeOwned {
+        basic_capacity: Uint64 => u64,
+        max_records: Uint32 => u32,
+        min_transfer_capacity: Uint64 => u64
+    };
+    ConfigCellRelease, ConfigCellReleaseReader => ConfigCellReleaseOwned {
+        lucky_number: Uint32 => u32
+    };
+    ConfigCellSecondaryMarket, ConfigCellSecondaryMarketReader => ConfigCellSecondaryMarketOwned {
+        common_fee: Uint64 => u64,
+        sale_min_price: Uint64 => u64,
+        sale_expiration_limit: Uint32 => u32,
+        sale_description_bytes_limit: Uint32 => u32,
+        sale_cell_basic_capacity: Uint64 => u64,
+        sale_cell_prepared_fee_capacity: Uint64 => u64,
+        auction_max_extendable_duration: Uint32 => u32,
+        auction_duration_increment_each_bid: Uint32 => u32,
+        auction_min_opening_price: Uint64 => u64,
+        auction_min_increment_rate_each_bid: Uint32 => u32,
+        auction_description_bytes_limit: Uint32 => u32,
+        auction_cell_basic_capacity: Uint64 => u64,
+        auction_cell_prepared_fee_capacity: Uint64 => u64,
+        offer_min_price: Uint64 => u64,
+        offer_cell_basic_capacity: Uint64 => u64,
+        offer_cell_prepared_fee_capacity: Uint64 => u64,
+        offer_message_bytes_limit: Uint32 => u32
+    };
+    ConfigCellReverseResolution, ConfigCellReverseResolutionReader => ConfigCellReverseResolutionOwned {
+        record_basic_capacity: Uint64 => u64,
+        record_prepared_fee_capacity: Uint64 => u64,
+        common_fee: Uint64 => u64
+    };
+    ConfigCellSubAccount, ConfigCellSubAccountReader => ConfigCellSubAccountOwned {
+        basic_capacity: Uint64 => u64,
+        prepared_fee_capacity: Uint64 => u64,
+        new_sub_account_price: Uint64 => u64,
+        renew_sub_account_price: Uint64 => u64,
+        common_fee: Uint64 => u64,
+        create_fee: Uint64 => u64,
+        edit_fee: Uint64 => u64,
+        renew_fee: Uint64 => u64,
+        recycle_fee: Uint64 => u64
+    };
+    ProposalCellData, ProposalCellDataReader => ProposalCellDataOwned {
+        proposer_lock: Script => ScriptOwned,
+        created_at_height: Uint64 => u64,
+        slices: SliceList => Vec<Vec<ProposalItemOwned>>
+    };
+    ProposalItem, ProposalItemReader => ProposalItemOwned {
+        account_id: AccountId => [u8; 20],
+        item_type: Uint8 => ProposalSliceItemType,
+        next: AccountId => [u8; 20]
+    };
+    IncomeCellData, IncomeCellDataReader => IncomeCellDataOwned {
+        creator: Script => ScriptOwned,
+        records: IncomeRecords => Vec<IncomeRecordOwned>
+    };
+    IncomeRecord, IncomeRecordReader => IncomeRecordOwned {
+        belong_to: Script => ScriptOwned,
+        capacity: Uint64 => u64
+    };
+    AccountCellDataV1, AccountCellDataV1Reader => AccountCellDataV1Owned {
+        id: AccountId => [u8; 20],
+        account: AccountChars => Vec<AccountCharOwned>,
+        registered_at: Uint64 => u64,
+        updated_at: Uint64 => u64,
+        status: Uint8 => AccountStatus,
+        records: Records => Vec<RecordOwned>
+    };
+    AccountCellDataV2, AccountCellDataV2Reader => AccountCellDataV2Owned {
+        id: AccountId => [u8; 20],
+        account: AccountChars => Vec<AccountCharOwned>,
+        registered_at: Uint64 => u64,
+        last_transfer_account_at: Uint64 => u64,
+        last_edit_manager_at: Uint64 => u64,
+        last_edit_records_at: Uint64 => u64,
+        status: Uint8 => AccountStatus,
+        records: Records => Vec<RecordOwned>
+    };
+    AccountCellData, AccountCellDataReader => AccountCellDataOwned {
+        id: AccountId => [u8; 20],
+        account: AccountChars => Vec<AccountCharOwned>,
+        registered_at: Uint64 => u64,
+        last_transfer_account_at: Uint64 => u64,
+        last_edit_manager_at: Uint64 => u64,
+        last_edit_records_at: Uint64 => u64,
+        status: Uint8 => AccountStatus,
+        records: Records => Vec<RecordOwned>,
+        enable_sub_account: Uint8 => SubAccountEnableStatus,
+        renew_sub_account_price: Uint64 => u64
+    };
+    Record, RecordReader => RecordOwned {
+        record_type: Bytes => Vec<u8>,
+        record_key: Bytes => Vec<u8>,
+        record_label: Bytes => Vec<u8>,
+        record_value: Bytes => Vec<u8>,
+        record_ttl: Uint32 => u32
+    };
+    AccountSaleCellDataV1, AccountSaleCellDataV1Reader => AccountSaleCellDataV1Owned {
+        account_id: AccountId => [u8; 20],
+        account: Bytes => String,
+        price: Uint64 => u64,
+        description: Bytes => Vec<u8>,
+        started_at: Uint64 => u64
+    };
+    AccountSaleCellData, AccountSaleCellDataReader => AccountSaleCellDataOwned {
+        account_id: AccountId => [u8; 20],
+        account: Bytes => String,
+        price: Uint64 => u64,
+        description: Bytes => Vec<u8>,
+        started_at: Uint64 => u64,
+        buyer_inviter_profit_rate: Uint32 => u32
+    };
+    AccountAuctionCellData, AccountAuctionCellDataReader => AccountAuctionCellDataOwned {
+        account_id: AccountId => [u8; 20],
+        account: Bytes => String,
+        description: Bytes => Vec<u8>,
+        opening_price: Uint64 => u64,
+        increment_rate_each_bid: Uint32 => u32,
+        started_at: Uint64 => u64,
+        ended_at: Uint64 => u64,
+        current_bidder_lock: Script => ScriptOwned,
+        current_bid_price: Uint64 => u64,
+        prev_bidder_profit_rate: Uint32 => u32
+    };
+    PreAccountCellData, PreAccountCellDataReader => PreAccountCellDataOwned {
+        account: AccountChars => Vec<AccountCharOwned>,
+        refund_lock: Script => ScriptOwned,
+        owner_lock_args: Bytes => Vec<u8>,
+        inviter_id: Bytes => Vec<u8>,
+        inviter_lock: ScriptOpt => Option<ScriptOwned>,
+        channel_lock: ScriptOpt => Option<ScriptOwned>,
+        price: PriceConfig => PriceConfigOwned,
+        quote: Uint64 => u64,
+        invited_discount: Uint32 => u32,
+        created_at: Uint64 => u64
+    };
+    AccountChar, AccountCharReader => AccountCharOwned {
+        char_set_name: Uint32 => CharSetType,
+        bytes: Bytes => String
+    };
+    OfferCellData, OfferCellDataReader => OfferCellDataOwned {
+        account: Bytes => String,
+        price: Uint64 => u64,
+        message: Bytes => Vec<u8>,
+        inviter_lock: Script => ScriptOwned,
+        channel_lock: Script => ScriptOwned
+    };
+    SubAccount, SubAccountReader => SubAccountOwned {
+        lock: Script => ScriptOwned,
+        id: AccountId => [u8; 20],
+        account: AccountChars => Vec<AccountCharOwned>,
+        suffix: Bytes => String,
+        registered_at: Uint64 => u64,
+        expired_at: Uint64 => u64,
+        status: Uint8 => AccountStatus,
+        records: Records => Vec<RecordOwned>,
+        nonce: Uint64 => u64,
+        enable_sub_account: Uint8 => SubAccountEnableStatus,
+        renew_sub_account_price: Uint64 => u64
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::append_field;
+    use alloc::vec;
+
+    fn account_cell_data() -> AccountCellDataOwned {
+        AccountCellDataOwned {
+            id: [1; 20],
+            account: vec![AccountCharOwned {
+                char_set_name: CharSetType::En,
+                bytes: String::from("a"),
+            }],
+            registered_at: 1,
+            last_transfer_account_at: 2,
+            last_edit_manager_at: 3,
+            last_edit_records_at: 4,
+            status: AccountStatus::Selling,
+            records: vec![RecordOwned {
+                record_type: b"address".to_vec(),
+                record_key: b"60".to_vec(),
+                record_label: vec![],
+                record_value: vec![0xff, 0x00],
+                record_ttl: 300,
+            }],
+            enable_sub_account: SubAccountEnableStatus::On,
+            renew_sub_account_price: 5,
+        }
+    }
+
+    #[test]
+    fn round_trip_is_byte_exact() {
+        let owned = account_cell_data();
+        let entity = AccountCellData::from(owned.clone());
+
+        assert_eq!(AccountCellDataOwned::try_from(entity.as_reader()).unwrap(), owned);
+        assert_eq!(
+            AccountCellData::from(AccountCellDataOwned::try_from(entity.clone()).unwrap()).as_slice(),
+            entity.as_slice()
+        );
+    }
+
+    #[test]
+    fn options_round_trip() {
+        let lock = Script::new_builder().hash_type(Byte::new(1)).build();
+        let entity = PreAccountCellData::new_builder()
+            .inviter_lock(ScriptOpt::new_builder().set(Some(lock)).build())
+            .build();
+        let owned = PreAccountCellDataOwned::try_from(entity.as_reader()).unwrap();
+
+        assert_eq!(owned.inviter_lock.as_ref().map(|lock| lock.hash_type), Some(1));
+        assert_eq!(owned.channel_lock, None);
+        assert_eq!(PreAccountCellData::from(owned).as_slice(), entity.as_slice());
+    }
+
+    #[test]
+    fn extra_fields_are_not_dropped_silently() {
+        let entity = AccountCellData::from(account_cell_data());
+        let extended = append_field(entity.as_slice(), &[1]);
+        let reader = AccountCellDataReader::from_compatible_slice(&extended).unwrap();
+
+        match AccountCellDataOwned::try_from(reader) {
+            Err(DasTypesError::LossyConversion(name)) => assert_eq!(name, AccountCellData::NAME),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn nested_extra_fields_are_not_dropped_silently() {
+        let record = Record::default();
+        let extended = append_field(record.as_slice(), &[1]);
+        let records = Records::new_builder()
+            .push(Record::new_unchecked(extended.into()))
+            .build();
+        let entity = AccountCellData::new_builder().records(records).build();
+
+        match AccountCellDataOwned::try_from(entity.as_reader()) {
+            Err(DasTypesError::LossyConversion(name)) => assert_eq!(name, AccountCellData::NAME),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        let entity = AccountSaleCellData::new_builder()
+            .account(Bytes::from(&[0xffu8, 0xfe][..]))
+            .build();
+        match AccountSaleCellDataOwned::try_from(entity.as_reader()) {
+            Err(DasTypesError::InvalidUtf8(_)) => {}
+            other => panic!("{:?}", other),
+        }
+
+        let entity = AccountCellData::new_builder().status(Uint8::from(99)).build();
+        match AccountCellDataOwned::try_from(entity.as_reader()) {
+            Err(DasTypesError::UnknownValue(_, 99)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+}
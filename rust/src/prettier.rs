@@ -1,544 +1,901 @@
-use super::schemas::packed::*;
-use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use super::{constants::ACCOUNT_SUFFIX, error::DasTypesError, schemas::packed::*};
+use alloc::string::String;
+use core::fmt::{self, Debug, Write};
 
 macro_rules! print_fields {
-    ($self:expr, $struct_name:expr, {$( $tt:tt ),+}) => {
-        String::from($struct_name) + "{"
-        $(+ &print_fields!(@field $self, $tt) + ", ")+
-        + "}"
+    ($self:expr, $printer:expr, $struct_name:expr, {$( $tt:tt ),+}) => {{
+        $printer.begin_struct($struct_name)?;
+        $(print_fields!(@field $self, $printer, $tt);)+
+        $printer.end_struct()
+    }};
+    (@field $self:expr, $printer:expr, $field:ident) => {
+        $printer.field(stringify!($field), &$self.$field())?
     };
-    (@field $self:expr, $field:ident) => {
-        String::from(stringify!($field)) + ": " + &$self.$field().as_prettier()
+    (@field $self:expr, $printer:expr, ($field:ident as $unit:ident)) => {
+        $printer.field(stringify!($field), &PrettyUnit(Unit::$unit, $self.$field()))?
+    };
+    (@field $self:expr, $printer:expr, ($field:ident -> $value:expr)) => {
+        $printer.field(stringify!($field), $value)?
     };
-    (@field $self:expr, ($field:ident -> $value:expr)) => {
-        String::from(stringify!($field)) + ": " + $value
-    }
-}
-
-pub trait Prettier {
-    fn as_prettier(&self) -> String;
 }
 
-/// For compatible with returning a string instead of any type.
-impl Prettier for String {
-    fn as_prettier(&self) -> String {
-        self.clone()
+/// Options of how the entities are printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrettyOptions {
+    /// Print every field and item on its own line.
+    pub multiline: bool,
+    /// The spaces of each indentation level, only works in multiline mode.
+    pub indent: usize,
+    /// Omit the type names of numbers and bytes, and the spaces between fields.
+    pub compact: bool,
+    /// The max number of bytes shown for bytes, the rest are replaced with their length.
+    pub max_bytes: Option<usize>,
+    /// Print numbers in hex instead of decimal.
+    pub hex_numbers: bool,
+    /// Print amounts, prices and timestamps in their real world units, and accounts next to their AccountIds, it is off
+    /// by default so that `as_prettier` keeps printing the raw values.
+    pub semantic: bool,
+}
+
+/// The real world units of Uint64 fields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    /// Amounts of CKB in shannons, printed as CKB.
+    Shannon,
+    /// Prices in USD accurate to 6 decimal places, printed as USD.
+    Usd,
+    /// Unix timestamps in seconds, printed as ISO-8601 date times in UTC.
+    Seconds,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        PrettyOptions {
+            multiline: false,
+            indent: 4,
+            compact: false,
+            max_bytes: None,
+            hex_numbers: false,
+            semantic: false,
+        }
     }
 }
 
-impl Prettier for Uint8 {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl PrettyOptions {
+    /// The options of printing entities as multi-line indented trees.
+    pub fn tree() -> Self {
+        PrettyOptions {
+            multiline: true,
+            ..Default::default()
+        }
     }
-}
 
-impl<'a> Prettier for Uint8Reader<'a> {
-    fn as_prettier(&self) -> String {
-        use core::convert::TryInto;
-        let number = self
-            .raw_data()
-            .try_into()
-            .map(u8::from_le_bytes)
-            .expect("Decoding Uint8 failed.");
-        format!("Uint8({})", number)
+    /// The options of printing entities as short as possible, which is suitable for contract logs.
+    pub fn compact() -> Self {
+        PrettyOptions {
+            compact: true,
+            max_bytes: Some(32),
+            ..Default::default()
+        }
     }
-}
 
-impl Prettier for Uint32 {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    /// The options of printing entities in one line with their real world units and readable accounts.
+    pub fn semantic() -> Self {
+        PrettyOptions {
+            semantic: true,
+            ..Default::default()
+        }
     }
 }
 
-impl<'a> Prettier for Uint32Reader<'a> {
-    fn as_prettier(&self) -> String {
-        use core::convert::TryInto;
-        let number = self
-            .raw_data()
-            .try_into()
-            .map(u32::from_le_bytes)
-            .expect("Decoding Uint32 failed.");
-        format!("Uint32({})", number)
-    }
+/// The writer of PrettyPrint which keeps the options and the state of indentation.
+pub struct Printer<'w> {
+    out: &'w mut dyn Write,
+    options: PrettyOptions,
+    depth: usize,
+    // If no field or item has been written since the last struct or list began.
+    first: bool,
 }
 
-impl Prettier for Uint64 {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'w> Printer<'w> {
+    pub fn new(out: &'w mut dyn Write, options: PrettyOptions) -> Self {
+        Printer {
+            out,
+            options,
+            depth: 0,
+            first: true,
+        }
     }
-}
 
-impl<'a> Prettier for Uint64Reader<'a> {
-    fn as_prettier(&self) -> String {
-        use core::convert::TryInto;
-        let number = self
-            .raw_data()
-            .try_into()
-            .map(u64::from_le_bytes)
-            .expect("Decoding Uint64 failed.");
-        format!("Uint64({})", number)
+    pub fn options(&self) -> &PrettyOptions {
+        &self.options
     }
-}
 
-impl Prettier for Timestamp {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    fn write_indent(&mut self, depth: usize) -> fmt::Result {
+        self.out.write_char('\n')?;
+        for _ in 0..depth * self.options.indent {
+            self.out.write_char(' ')?;
+        }
+        Ok(())
     }
-}
 
-impl<'a> Prettier for TimestampReader<'a> {
-    fn as_prettier(&self) -> String {
-        use core::convert::TryInto;
-        let number = self
-            .raw_data()
-            .try_into()
-            .map(u64::from_le_bytes)
-            .expect("Decoding Uint64 failed.");
-        format!("Uint64({})", number)
+    fn write_separator(&mut self) -> fmt::Result {
+        if self.options.multiline {
+            self.write_indent(self.depth)
+        } else if self.options.compact && !self.first {
+            self.out.write_char(',')
+        } else {
+            Ok(())
+        }
     }
-}
 
-impl Prettier for Byte {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    fn write_terminator(&mut self) -> fmt::Result {
+        self.first = false;
+        if self.options.multiline {
+            self.out.write_char(',')
+        } else if self.options.compact {
+            Ok(())
+        } else {
+            self.out.write_str(", ")
+        }
     }
-}
 
-impl<'a> Prettier for ByteReader<'a> {
-    fn as_prettier(&self) -> String {
-        use molecule::hex_string;
-        let raw_data = hex_string(&self.as_slice());
+    fn write_close(&mut self, close: char) -> fmt::Result {
+        self.depth -= 1;
+        if self.options.multiline && !self.first {
+            self.write_indent(self.depth)?;
+        }
+        self.first = false;
+        self.out.write_char(close)
+    }
 
-        format!("Bytes(0x{})", raw_data)
+    pub fn begin_struct(&mut self, name: &str) -> fmt::Result {
+        self.out.write_str(name)?;
+        self.out.write_str(if self.options.multiline && !self.options.compact {
+            " {"
+        } else {
+            "{"
+        })?;
+        self.depth += 1;
+        self.first = true;
+        Ok(())
+    }
+
+    pub fn field(&mut self, name: &str, value: &dyn PrettyPrint) -> fmt::Result {
+        self.write_separator()?;
+        self.out.write_str(name)?;
+        self.out.write_str(if self.options.compact && !self.options.multiline {
+            ":"
+        } else {
+            ": "
+        })?;
+        value.pretty_print(self)?;
+        self.write_terminator()
     }
-}
 
-impl Prettier for Bytes {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    pub fn end_struct(&mut self) -> fmt::Result {
+        self.write_close('}')
     }
-}
 
-impl<'a> Prettier for BytesReader<'a> {
-    fn as_prettier(&self) -> String {
-        use molecule::hex_string;
-        let raw_data = hex_string(&self.raw_data());
+    pub fn begin_list(&mut self) -> fmt::Result {
+        self.out.write_str(if self.options.multiline || self.options.compact {
+            "["
+        } else {
+            "[ "
+        })?;
+        self.depth += 1;
+        self.first = true;
+        Ok(())
+    }
 
-        format!("Bytes(0x{})", raw_data)
+    pub fn item(&mut self, value: &dyn PrettyPrint) -> fmt::Result {
+        self.write_separator()?;
+        value.pretty_print(self)?;
+        self.write_terminator()
     }
-}
 
-impl Prettier for Hash {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    pub fn end_list(&mut self) -> fmt::Result {
+        self.write_close(']')
     }
-}
 
-impl<'a> Prettier for HashReader<'a> {
-    fn as_prettier(&self) -> String {
-        use molecule::hex_string;
-        let raw_data = hex_string(&self.raw_data());
+    /// Print a number as `Type(number)`, or only the number in compact mode.
+    pub fn number(&mut self, type_name: &str, value: u64) -> fmt::Result {
+        if !self.options.compact {
+            self.out.write_str(type_name)?;
+            self.out.write_char('(')?;
+        }
+        if self.options.hex_numbers {
+            write!(self.out, "{:#x}", value)?;
+        } else {
+            write!(self.out, "{}", value)?;
+        }
+        if !self.options.compact {
+            self.out.write_char(')')?;
+        }
+        Ok(())
+    }
 
-        format!("Bytes(0x{})", raw_data)
+    /// Print bytes as `Type(0x...)`, or only the hex in compact mode.
+    pub fn bytes(&mut self, type_name: &str, data: &[u8]) -> fmt::Result {
+        if !self.options.compact {
+            self.out.write_str(type_name)?;
+            self.out.write_char('(')?;
+        }
+        self.write_hex(data)?;
+        if !self.options.compact {
+            self.out.write_char(')')?;
+        }
+        Ok(())
     }
-}
 
-impl Prettier for Script {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    fn write_hex(&mut self, data: &[u8]) -> fmt::Result {
+        let shown = match self.options.max_bytes {
+            Some(max) if max < data.len() => max,
+            _ => data.len(),
+        };
+        self.out.write_str("0x")?;
+        for byte in &data[..shown] {
+            write!(self.out, "{:02x}", byte)?;
+        }
+        if shown < data.len() {
+            write!(self.out, "...({} bytes)", data.len())?;
+        }
+        Ok(())
     }
-}
 
-impl<'a> Prettier for ScriptReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "Script", {
-            code_hash,
-            hash_type,
-            args
-        })
+    /// Print a number in its real world unit, or as `Type(number)` when semantic is off.
+    pub fn number_in(&mut self, type_name: &str, value: u64, unit: Unit) -> fmt::Result {
+        if !self.options.semantic {
+            return self.number(type_name, value);
+        }
+        match unit {
+            Unit::Shannon => {
+                self.write_decimal(value, 8)?;
+                self.out.write_str(" CKB")
+            }
+            Unit::Usd => {
+                self.write_decimal(value, 6)?;
+                self.out.write_str(" USD")
+            }
+            Unit::Seconds => self.write_datetime(value),
+        }
     }
-}
 
-impl Prettier for ScriptOpt {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    // is_multiple_of is too new for the toolchain of contracts.
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    fn write_decimal(&mut self, value: u64, decimals: u32) -> fmt::Result {
+        let base = 10u64.pow(decimals);
+        write!(self.out, "{}", value / base)?;
+        let mut fraction = value % base;
+        if fraction != 0 {
+            let mut width = decimals as usize;
+            while fraction % 10 == 0 {
+                fraction /= 10;
+                width -= 1;
+            }
+            write!(self.out, ".{:0width$}", fraction, width = width)?;
+        }
+        Ok(())
+    }
+
+    fn write_datetime(&mut self, timestamp: u64) -> fmt::Result {
+        let (days, seconds) = (timestamp / 86400, timestamp % 86400);
+        // Convert the days since 1970-01-01 to the civil date, see http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        write!(
+            self.out,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
     }
-}
 
-impl<'a> Prettier for ScriptOptReader<'a> {
-    fn as_prettier(&self) -> String {
-        if let Some(v) = self.to_opt() {
-            format!("ScriptOpt(Some({}))", v.as_prettier())
-        } else {
-            String::from("ScriptOpt(None)")
+    /// Print bytes as UTF-8 text, invalid sequences are replaced with U+FFFD instead of failing.
+    pub fn text(&mut self, data: &[u8]) -> fmt::Result {
+        let mut rest = data;
+        loop {
+            match core::str::from_utf8(rest) {
+                Ok(valid) => return self.out.write_str(valid),
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    // SAFETY: the bytes before valid_up_to are checked to be valid UTF-8.
+                    self.out.write_str(unsafe { core::str::from_utf8_unchecked(valid) })?;
+                    self.out.write_char(char::REPLACEMENT_CHARACTER)?;
+                    match err.error_len() {
+                        Some(len) => rest = &invalid[len..],
+                        None => return Ok(()),
+                    }
+                }
+            }
         }
     }
-}
 
-impl Prettier for OutPoint {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+    /// Print the error in place of the value which can not be decoded.
+    pub fn error(&mut self, err: DasTypesError) -> fmt::Result {
+        write!(self.out, "Error({})", err)
     }
 }
 
-impl<'a> Prettier for OutPointReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "OutPoint", {
-            tx_hash,
-            index
-        })
+impl<'w> Write for Printer<'w> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_str(s)
     }
 }
 
-impl Prettier for AccountId {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+/// Print entities into any fmt::Write with PrettyOptions.
+pub trait PrettyPrint {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result;
+
+    fn write_pretty(&self, out: &mut dyn Write, options: PrettyOptions) -> fmt::Result {
+        self.pretty_print(&mut Printer::new(out, options))
+    }
+
+    fn to_pretty_string(&self, options: PrettyOptions) -> String {
+        let mut ret = String::new();
+        // Writing into a String never fails, only the entity itself may be broken.
+        let _ = self.write_pretty(&mut ret, options);
+        ret
+    }
+
+    /// Wrap the entity for formatting with `{}`, e.g. `debug!("{}", data.pretty(PrettyOptions::compact()))`.
+    fn pretty(&self, options: PrettyOptions) -> Pretty<'_, Self>
+    where
+        Self: Sized,
+    {
+        Pretty(self, options)
     }
 }
 
-impl<'a> Prettier for AccountIdReader<'a> {
-    fn as_prettier(&self) -> String {
-        use molecule::hex_string;
-        let raw_data = hex_string(&self.raw_data());
+/// The Display adapter of PrettyPrint.
+pub struct Pretty<'a, T: ?Sized>(&'a T, PrettyOptions);
 
-        format!("AccountId(0x{})", raw_data)
+impl<'a, T: PrettyPrint + ?Sized> fmt::Display for Pretty<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_pretty(f, self.1)
     }
 }
 
-impl Prettier for AccountChars {
+pub trait Prettier {
+    fn as_prettier(&self) -> String;
+}
+
+/// Print in one line with the default options.
+impl<T: PrettyPrint + ?Sized> Prettier for T {
     fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+        self.to_pretty_string(PrettyOptions::default())
     }
 }
 
-impl<'a> Prettier for AccountCharsReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = Vec::new();
-        for reader in self.iter() {
-            ret.append(&mut reader.bytes().raw_data().to_owned());
-        }
+/// Print the name of the enum decoded from a field, or the raw number if it is unknown.
+struct PrettyEnum<'r, T>(&'static str, Result<T, DasTypesError>, &'r dyn PrettyPrint);
 
-        format!(
-            "AccountChars({})",
-            String::from_utf8(ret).expect("AccountChars should contains only utf8 chars.")
-        )
+impl<'r, T: Debug> PrettyPrint for PrettyEnum<'r, T> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        match &self.1 {
+            Ok(v) if printer.options().compact => write!(printer, "{:?}", v),
+            Ok(v) => write!(printer, "{}::{:?}", self.0, v),
+            Err(_) => self.2.pretty_print(printer),
+        }
     }
 }
 
-impl Prettier for PreAccountCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+/// Print a Uint64 field in its real world unit.
+struct PrettyUnit<'r>(Unit, Uint64Reader<'r>);
+
+impl<'r> PrettyPrint for PrettyUnit<'r> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        use core::convert::TryInto;
+        match self.1.raw_data().try_into().map(u64::from_le_bytes) {
+            Ok(number) => printer.number_in("Uint64", number, self.0),
+            Err(_) => self.1.pretty_print(printer),
+        }
     }
 }
 
-impl<'a> Prettier for PreAccountCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "PreAccountCellData", {
-            account,
-            refund_lock,
-            owner_lock_args,
-            inviter_id,
-            inviter_lock,
-            channel_lock,
-            price,
-            quote,
-            invited_discount,
-            created_at
-        })
-    }
+/// The readable account which is printed next to its AccountId.
+enum AccountName<'r> {
+    // The chars of the account and its suffix.
+    Chars(AccountCharsReader<'r>, &'r [u8]),
+    // The whole account in bytes.
+    Bytes(BytesReader<'r>),
 }
 
-impl Prettier for Records {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'r> PrettyPrint for AccountName<'r> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        match self {
+            AccountName::Chars(chars, suffix) => {
+                for reader in chars.iter() {
+                    printer.text(reader.bytes().raw_data())?;
+                }
+                printer.text(suffix)
+            }
+            AccountName::Bytes(bytes) if printer.options().semantic => printer.text(bytes.raw_data()),
+            AccountName::Bytes(bytes) => bytes.pretty_print(printer),
+        }
     }
 }
 
-impl<'a> Prettier for RecordsReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = String::from("[ ");
+/// Print the AccountId with its readable account, e.g. `AccountId(0x..., das.bit)`.
+struct PrettyAccountId<'r>(AccountIdReader<'r>, AccountName<'r>);
 
-        for item in self.iter() {
-            ret = ret + &item.as_prettier() + ", ";
+impl<'r> PrettyPrint for PrettyAccountId<'r> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        if !printer.options().semantic {
+            return self.0.pretty_print(printer);
         }
-
-        ret + "]"
+        let compact = printer.options().compact;
+        if !compact {
+            printer.write_str("AccountId(")?;
+        }
+        printer.write_hex(self.0.raw_data())?;
+        printer.write_str(if compact { "(" } else { ", " })?;
+        self.1.pretty_print(printer)?;
+        printer.write_str(")")
     }
 }
 
-impl Prettier for Record {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+/// Print bytes as lossy UTF-8 text.
+struct PrettyText<'r>(&'r [u8]);
+
+impl<'r> PrettyPrint for PrettyText<'r> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.text(self.0)
     }
 }
 
-impl<'a> Prettier for RecordReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "Record", {
-            record_type,
-            record_key,
-            record_label,
-            record_value,
-            record_ttl
-        })
+/// For compatible with returning a string instead of any type.
+impl PrettyPrint for str {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(self)
     }
 }
 
-impl Prettier for AccountCellDataV2 {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl PrettyPrint for String {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str(self)
     }
 }
 
-impl<'a> Prettier for AccountCellDataV2Reader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "AccountCellDataV2", {
-            id,
-            account,
-            registered_at,
-            last_transfer_account_at,
-            last_edit_manager_at,
-            last_edit_records_at,
-            status,
-            records
-        })
+macro_rules! impl_pretty_entity {
+    ($( $entity:ident ),+) => {
+        $(
+            impl PrettyPrint for $entity {
+                fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+                    self.as_reader().pretty_print(printer)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_pretty_uint {
+    ($( $reader:ident, $uint:ty, $type_name:expr );+) => {
+        $(
+            impl<'a> PrettyPrint for $reader<'a> {
+                fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+                    use core::convert::TryInto;
+                    match self.raw_data().try_into().map(<$uint>::from_le_bytes) {
+                        Ok(number) => printer.number($type_name, number as u64),
+                        Err(_) => printer.error(DasTypesError::LengthNotMatch(
+                            stringify!($reader),
+                            core::mem::size_of::<$uint>(),
+                            self.raw_data().len(),
+                        )),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_pretty_list {
+    ($( $reader:ident ),+) => {
+        $(
+            impl<'a> PrettyPrint for $reader<'a> {
+                fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+                    printer.begin_list()?;
+                    for item in self.iter() {
+                        printer.item(&item)?;
+                    }
+                    printer.end_list()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_pretty_option {
+    ($( $reader:ident, $type_name:expr );+) => {
+        $(
+            impl<'a> PrettyPrint for $reader<'a> {
+                fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+                    printer.write_str($type_name)?;
+                    if let Some(v) = self.to_opt() {
+                        printer.write_str("(Some(")?;
+                        v.pretty_print(printer)?;
+                        printer.write_str("))")
+                    } else {
+                        printer.write_str("(None)")
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_pretty_entity!(
+    Uint8,
+    Uint32,
+    Uint64,
+    Timestamp,
+    Byte,
+    Bytes,
+    Hash,
+    HashOpt,
+    Script,
+    ScriptOpt,
+    OutPoint,
+    Data,
+    DataEntity,
+    DataEntityOpt,
+    AccountId,
+    AccountChars,
+    AccountChar,
+    PreAccountCellData,
+    Records,
+    Record,
+    AccountCellDataV2,
+    AccountCellData,
+    AccountCellDataV1,
+    AccountSaleCellDataV1,
+    AccountSaleCellData,
+    AccountAuctionCellData,
+    OfferCellData,
+    IncomeCellData,
+    IncomeRecords,
+    IncomeRecord,
+    ProposalCellData,
+    SliceList,
+    SL,
+    ProposalItem,
+    ActionData,
+    ConfigCellMain,
+    TypeIdTable,
+    DasLockOutPointTable,
+    ConfigCellAccount,
+    ConfigCellApply,
+    Chars,
+    ConfigCellPrice,
+    DiscountConfig,
+    PriceConfigList,
+    PriceConfig,
+    ConfigCellProposal,
+    ConfigCellProfitRate,
+    ConfigCellIncome,
+    ConfigCellSecondaryMarket,
+    ConfigCellReverseResolution,
+    ConfigCellSubAccount,
+    ConfigCellRelease,
+    SubAccount
+);
+
+impl_pretty_uint!(
+    Uint8Reader, u8, "Uint8";
+    Uint32Reader, u32, "Uint32";
+    Uint64Reader, u64, "Uint64"
+);
+
+impl<'a> PrettyPrint for TimestampReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        use core::convert::TryInto;
+        match self.raw_data().try_into().map(u64::from_le_bytes) {
+            Ok(number) => printer.number_in("Timestamp", number, Unit::Seconds),
+            Err(_) => printer.error(DasTypesError::LengthNotMatch(
+                "TimestampReader",
+                8,
+                self.raw_data().len(),
+            )),
+        }
     }
 }
 
-impl Prettier for AccountCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl_pretty_list!(
+    RecordsReader,
+    IncomeRecordsReader,
+    SliceListReader,
+    SLReader,
+    CharsReader,
+    PriceConfigListReader
+);
+
+impl<'a> PrettyPrint for ByteReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.bytes("Bytes", self.as_slice())
     }
 }
 
-impl<'a> Prettier for AccountCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "AccountCellData", {
-            id,
-            account,
-            registered_at,
-            last_transfer_account_at,
-            last_edit_manager_at,
-            last_edit_records_at,
-            status,
-            records,
-            enable_sub_account,
-            renew_sub_account_price
-        })
+impl<'a> PrettyPrint for BytesReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.bytes("Bytes", self.raw_data())
     }
 }
 
-impl Prettier for AccountSaleCellDataV1 {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for HashReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.bytes("Bytes", self.raw_data())
     }
 }
 
-impl<'a> Prettier for AccountSaleCellDataV1Reader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "AccountSaleCellDataV1", {
-            account_id,
-            account,
-            price,
-            description,
-            started_at
-        })
+impl<'a> PrettyPrint for AccountIdReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.bytes("AccountId", self.raw_data())
     }
 }
 
-impl Prettier for AccountSaleCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl_pretty_option!(
+    HashOptReader, "HashOpt";
+    ScriptOptReader, "ScriptOpt";
+    DataEntityOptReader, "DataEntityOpt"
+);
+
+impl<'a> PrettyPrint for AccountCharsReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        printer.write_str("AccountChars(")?;
+        for reader in self.iter() {
+            printer.text(reader.bytes().raw_data())?;
+        }
+        printer.write_str(")")
     }
 }
 
-impl<'a> Prettier for AccountSaleCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "AccountSaleCellData", {
-            account_id,
-            account,
-            price,
-            description,
-            started_at,
-            buyer_inviter_profit_rate
+impl<'a> PrettyPrint for AccountCharReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountChar", {
+            (char_set_name -> &PrettyEnum("CharSetType", self.char_set_name_typed(), &self.char_set_name())),
+            (bytes -> &PrettyText(self.bytes().raw_data()))
         })
     }
 }
 
-impl Prettier for OfferCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for ScriptReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "Script", {
+            code_hash,
+            hash_type,
+            args
+        })
     }
 }
 
-impl<'a> Prettier for OfferCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "AccountSaleCellData", {
-            account,
-            price,
-            message,
-            inviter_lock,
-            channel_lock
+impl<'a> PrettyPrint for OutPointReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "OutPoint", {
+            tx_hash,
+            index
         })
     }
 }
 
-impl Prettier for IncomeCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for DataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "Data", {
+            dep,
+            old,
+            new
+        })
     }
 }
 
-impl<'a> Prettier for IncomeCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "IncomeCellData", {
-            creator,
-            records
+impl<'a> PrettyPrint for DataEntityReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "DataEntity", {
+            index,
+            version,
+            entity
         })
     }
 }
 
-impl Prettier for IncomeRecords {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for PreAccountCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "PreAccountCellData", {
+            account,
+            refund_lock,
+            owner_lock_args,
+            inviter_id,
+            inviter_lock,
+            channel_lock,
+            price,
+            (quote as Usd),
+            invited_discount,
+            (created_at as Seconds)
+        })
     }
 }
 
-impl<'a> Prettier for IncomeRecordsReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = String::from("[ ");
-
-        for item in self.iter() {
-            ret = ret + &item.as_prettier() + ", ";
-        }
-
-        ret + "]"
+impl<'a> PrettyPrint for RecordReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "Record", {
+            record_type,
+            record_key,
+            record_label,
+            record_value,
+            record_ttl
+        })
     }
 }
 
-impl Prettier for IncomeRecord {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for AccountCellDataV2Reader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountCellDataV2", {
+            (id -> &PrettyAccountId(self.id(), AccountName::Chars(self.account(), ACCOUNT_SUFFIX.as_bytes()))),
+            account,
+            (registered_at as Seconds),
+            (last_transfer_account_at as Seconds),
+            (last_edit_manager_at as Seconds),
+            (last_edit_records_at as Seconds),
+            (status -> &PrettyEnum("AccountStatus", self.status_typed(), &self.status())),
+            records
+        })
     }
 }
 
-impl<'a> Prettier for IncomeRecordReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "IncomeRecord", {
-            belong_to,
-            capacity
+impl<'a> PrettyPrint for AccountCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountCellData", {
+            (id -> &PrettyAccountId(self.id(), AccountName::Chars(self.account(), ACCOUNT_SUFFIX.as_bytes()))),
+            account,
+            (registered_at as Seconds),
+            (last_transfer_account_at as Seconds),
+            (last_edit_manager_at as Seconds),
+            (last_edit_records_at as Seconds),
+            (status -> &PrettyEnum("AccountStatus", self.status_typed(), &self.status())),
+            records,
+            (enable_sub_account -> &PrettyEnum("SubAccountEnableStatus", self.enable_sub_account_typed(), &self.enable_sub_account())),
+            (renew_sub_account_price as Shannon)
         })
     }
 }
 
-impl Prettier for ProposalCellData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for AccountCellDataV1Reader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountCellDataV1", {
+            (id -> &PrettyAccountId(self.id(), AccountName::Chars(self.account(), ACCOUNT_SUFFIX.as_bytes()))),
+            account,
+            (registered_at as Seconds),
+            (updated_at as Seconds),
+            (status -> &PrettyEnum("AccountStatus", self.status_typed(), &self.status())),
+            records
+        })
     }
 }
 
-impl<'a> Prettier for ProposalCellDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ProposalCellData", {
-            proposer_lock,
-            created_at_height,
-            slices
+impl<'a> PrettyPrint for AccountSaleCellDataV1Reader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountSaleCellDataV1", {
+            (account_id -> &PrettyAccountId(self.account_id(), AccountName::Bytes(self.account()))),
+            (account -> &AccountName::Bytes(self.account())),
+            (price as Shannon),
+            description,
+            (started_at as Seconds)
         })
     }
 }
 
-impl Prettier for SliceList {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for AccountSaleCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountSaleCellData", {
+            (account_id -> &PrettyAccountId(self.account_id(), AccountName::Bytes(self.account()))),
+            (account -> &AccountName::Bytes(self.account())),
+            (price as Shannon),
+            description,
+            (started_at as Seconds),
+            buyer_inviter_profit_rate
+        })
     }
 }
 
-impl<'a> Prettier for SliceListReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = String::from("[ ");
-
-        for item in self.iter() {
-            ret = ret + &item.as_prettier() + ", ";
-        }
-
-        ret + "]"
+impl<'a> PrettyPrint for AccountAuctionCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "AccountAuctionCellData", {
+            (account_id -> &PrettyAccountId(self.account_id(), AccountName::Bytes(self.account()))),
+            (account -> &AccountName::Bytes(self.account())),
+            description,
+            (opening_price as Shannon),
+            increment_rate_each_bid,
+            (started_at as Seconds),
+            (ended_at as Seconds),
+            current_bidder_lock,
+            (current_bid_price as Shannon),
+            prev_bidder_profit_rate
+        })
     }
 }
 
-impl Prettier for SL {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for OfferCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "OfferCellData", {
+            (account -> &AccountName::Bytes(self.account())),
+            (price as Shannon),
+            message,
+            inviter_lock,
+            channel_lock
+        })
     }
 }
 
-impl<'a> Prettier for SLReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = String::from("[ ");
-
-        for item in self.iter() {
-            ret = ret + &item.as_prettier() + ", ";
-        }
-
-        ret + "]"
+impl<'a> PrettyPrint for IncomeCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "IncomeCellData", {
+            creator,
+            records
+        })
     }
 }
 
-impl Prettier for ProposalItem {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for IncomeRecordReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "IncomeRecord", {
+            belong_to,
+            (capacity as Shannon)
+        })
     }
 }
 
-impl<'a> Prettier for ProposalItemReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ProposalItem", {
-            account_id,
-            item_type,
-            next
+impl<'a> PrettyPrint for ProposalCellDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ProposalCellData", {
+            proposer_lock,
+            created_at_height,
+            slices
         })
     }
 }
 
-impl Prettier for ActionData {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for ProposalItemReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ProposalItem", {
+            account_id,
+            (item_type -> &PrettyEnum("ProposalSliceItemType", self.item_type_typed(), &self.item_type())),
+            next
+        })
     }
 }
 
-impl<'a> Prettier for ActionDataReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ActionData", {
+impl<'a> PrettyPrint for ActionDataReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ActionData", {
             action,
             params
         })
     }
 }
 
-impl Prettier for ConfigCellMain {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellMainReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellMain", {
-            status,
+impl<'a> PrettyPrint for ConfigCellMainReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellMain", {
+            (status -> &PrettyEnum("SystemStatus", self.system_status(), &self.status())),
             type_id_table,
             das_lock_out_point_table
         })
     }
 }
 
-impl Prettier for TypeIdTable {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for TypeIdTableReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "TypeIdTable", {
+impl<'a> PrettyPrint for TypeIdTableReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "TypeIdTable", {
             account_cell,
             apply_register_cell,
             balance_cell,
@@ -554,43 +911,32 @@ impl<'a> Prettier for TypeIdTableReader<'a> {
     }
 }
 
-impl Prettier for DasLockOutPointTable {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for DasLockOutPointTableReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "TypeIdTable", {
+impl<'a> PrettyPrint for DasLockOutPointTableReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "DasLockOutPointTable", {
             ckb_signall,
             ckb_multisign,
             ckb_anyone_can_pay,
             eth,
-            tron
+            tron,
+            ed25519
         })
     }
 }
 
-impl Prettier for ConfigCellAccount {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellAccountReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellAccount", {
+impl<'a> PrettyPrint for ConfigCellAccountReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellAccount", {
             max_length,
-            basic_capacity,
-            prepared_fee_capacity,
+            (basic_capacity as Shannon),
+            (prepared_fee_capacity as Shannon),
             expiration_grace_period,
             record_min_ttl,
             record_size_limit,
-            transfer_account_fee,
-            edit_manager_fee,
-            edit_records_fee,
-            common_fee,
+            (transfer_account_fee as Shannon),
+            (edit_manager_fee as Shannon),
+            (edit_records_fee as Shannon),
+            (common_fee as Shannon),
             transfer_account_throttle,
             edit_manager_throttle,
             edit_records_throttle,
@@ -599,91 +945,43 @@ impl<'a> Prettier for ConfigCellAccountReader<'a> {
     }
 }
 
-impl Prettier for ConfigCellApply {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellApplyReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellApply", {
+impl<'a> PrettyPrint for ConfigCellApplyReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellApply", {
             apply_min_waiting_block_number,
             apply_max_waiting_block_number
         })
     }
 }
 
-impl Prettier for ConfigCellPrice {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellPriceReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellApply", {
+impl<'a> PrettyPrint for ConfigCellPriceReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellPrice", {
             discount,
             prices
         })
     }
 }
 
-impl Prettier for DiscountConfig {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for DiscountConfigReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "DiscountConfig", { invited_discount })
-    }
-}
-
-impl Prettier for PriceConfigList {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for PriceConfigListReader<'a> {
-    fn as_prettier(&self) -> String {
-        let mut ret = String::from("[ ");
-
-        for item in self.iter() {
-            ret = ret + &item.as_prettier() + ", ";
-        }
-
-        ret + "]"
-    }
-}
-
-impl Prettier for PriceConfig {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
+impl<'a> PrettyPrint for DiscountConfigReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "DiscountConfig", { invited_discount })
     }
 }
 
-impl<'a> Prettier for PriceConfigReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "DiscountConfig", {
+impl<'a> PrettyPrint for PriceConfigReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "PriceConfig", {
             length,
-            new,
-            renew
+            (new as Usd),
+            (renew as Usd)
         })
     }
 }
 
-impl Prettier for ConfigCellProposal {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellProposalReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellProposal", {
+impl<'a> PrettyPrint for ConfigCellProposalReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellProposal", {
             proposal_min_confirm_interval,
             proposal_min_extend_interval,
             proposal_min_recycle_interval,
@@ -693,15 +991,9 @@ impl<'a> Prettier for ConfigCellProposalReader<'a> {
     }
 }
 
-impl Prettier for ConfigCellProfitRate {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellProfitRateReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellProfitRate", {
+impl<'a> PrettyPrint for ConfigCellProfitRateReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellProfitRate", {
             inviter,
             channel,
             proposal_create,
@@ -718,123 +1010,334 @@ impl<'a> Prettier for ConfigCellProfitRateReader<'a> {
     }
 }
 
-impl Prettier for ConfigCellIncome {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellIncomeReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellIncome", {
-            basic_capacity,
+impl<'a> PrettyPrint for ConfigCellIncomeReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellIncome", {
+            (basic_capacity as Shannon),
             max_records,
-            min_transfer_capacity
+            (min_transfer_capacity as Shannon)
         })
     }
 }
 
-impl Prettier for ConfigCellSecondaryMarket {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellSecondaryMarketReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellSecondaryMarket", {
-            common_fee,
-            sale_min_price,
+impl<'a> PrettyPrint for ConfigCellSecondaryMarketReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellSecondaryMarket", {
+            (common_fee as Shannon),
+            (sale_min_price as Shannon),
             sale_expiration_limit,
             sale_description_bytes_limit,
-            sale_cell_basic_capacity,
-            sale_cell_prepared_fee_capacity,
+            (sale_cell_basic_capacity as Shannon),
+            (sale_cell_prepared_fee_capacity as Shannon),
             auction_max_extendable_duration,
             auction_duration_increment_each_bid,
-            auction_min_opening_price,
+            (auction_min_opening_price as Shannon),
             auction_min_increment_rate_each_bid,
             auction_description_bytes_limit,
-            auction_cell_basic_capacity,
-            auction_cell_prepared_fee_capacity,
-            offer_min_price,
-            offer_cell_basic_capacity,
-            offer_cell_prepared_fee_capacity,
+            (auction_cell_basic_capacity as Shannon),
+            (auction_cell_prepared_fee_capacity as Shannon),
+            (offer_min_price as Shannon),
+            (offer_cell_basic_capacity as Shannon),
+            (offer_cell_prepared_fee_capacity as Shannon),
             offer_message_bytes_limit
         })
     }
 }
 
-impl Prettier for ConfigCellReverseResolution {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellReverseResolutionReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellReverseResolution", {
-            record_basic_capacity,
-            record_prepared_fee_capacity,
-            common_fee
+impl<'a> PrettyPrint for ConfigCellReverseResolutionReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellReverseResolution", {
+            (record_basic_capacity as Shannon),
+            (record_prepared_fee_capacity as Shannon),
+            (common_fee as Shannon)
         })
     }
 }
 
-impl Prettier for ConfigCellSubAccount {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellSubAccountReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellSubAccount", {
-            basic_capacity,
-            prepared_fee_capacity,
-            new_sub_account_price,
-            renew_sub_account_price,
-            common_fee,
-            create_fee,
-            edit_fee,
-            renew_fee,
-            recycle_fee
+impl<'a> PrettyPrint for ConfigCellSubAccountReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellSubAccount", {
+            (basic_capacity as Shannon),
+            (prepared_fee_capacity as Shannon),
+            (new_sub_account_price as Shannon),
+            (renew_sub_account_price as Shannon),
+            (common_fee as Shannon),
+            (create_fee as Shannon),
+            (edit_fee as Shannon),
+            (renew_fee as Shannon),
+            (recycle_fee as Shannon)
         })
     }
 }
 
-impl Prettier for ConfigCellRelease {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for ConfigCellReleaseReader<'a> {
-    fn as_prettier(&self) -> String {
-        print_fields!(self, "ConfigCellRelease", { lucky_number })
+impl<'a> PrettyPrint for ConfigCellReleaseReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "ConfigCellRelease", { lucky_number })
     }
 }
 
-impl Prettier for SubAccount {
-    fn as_prettier(&self) -> String {
-        self.as_reader().as_prettier()
-    }
-}
-
-impl<'a> Prettier for SubAccountReader<'a> {
-    fn as_prettier(&self) -> String {
-        let fmt_suffix = String::from_utf8(self.suffix().raw_data().to_vec()).expect("Encoding utf-8 failed.");
-        print_fields!(self, "SubAccount", {
+impl<'a> PrettyPrint for SubAccountReader<'a> {
+    fn pretty_print(&self, printer: &mut Printer) -> fmt::Result {
+        print_fields!(self, printer, "SubAccount", {
             lock,
-            id,
+            (id -> &PrettyAccountId(self.id(), AccountName::Chars(self.account(), self.suffix().raw_data()))),
             account,
-            (suffix -> &fmt_suffix),
-            registered_at,
-            expired_at,
-            status,
+            (suffix -> &PrettyText(self.suffix().raw_data())),
+            (registered_at as Seconds),
+            (expired_at as Seconds),
+            (status -> &PrettyEnum("AccountStatus", self.status_typed(), &self.status())),
             records,
             nonce,
-            enable_sub_account,
-            renew_sub_account_price
+            (enable_sub_account -> &PrettyEnum("SubAccountEnableStatus", self.enable_sub_account_typed(), &self.enable_sub_account())),
+            (renew_sub_account_price as Shannon)
         })
     }
 }
+
+// Fail to compile when any type of the schemas has no PrettyPrint, the list is generated by build.rs.
+#[allow(dead_code)]
+mod coverage {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/prettier_check.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use molecule::prelude::*;
+
+    fn records() -> Records {
+        let record = Record::new_builder()
+            .record_key(Bytes::from(&b"a"[..]))
+            .record_ttl(Uint32::from(300))
+            .build();
+        Records::new_builder().push(record).build()
+    }
+
+    #[test]
+    fn tree_is_indented_by_depth() {
+        let expected = "[
+    Record {
+        record_type: Bytes(0x),
+        record_key: Bytes(0x61),
+        record_label: Bytes(0x),
+        record_value: Bytes(0x),
+        record_ttl: Uint32(300),
+    },
+]";
+        assert_eq!(records().to_pretty_string(PrettyOptions::tree()), expected);
+
+        let options = PrettyOptions {
+            indent: 2,
+            ..PrettyOptions::tree()
+        };
+        assert!(records()
+            .to_pretty_string(options)
+            .starts_with("[\n  Record {\n    record_type: "));
+        assert_eq!(Records::default().to_pretty_string(PrettyOptions::tree()), "[]");
+    }
+
+    #[test]
+    fn compact_omits_type_names_and_spaces() {
+        assert_eq!(
+            records().to_pretty_string(PrettyOptions::compact()),
+            "[Record{record_type:0x,record_key:0x61,record_label:0x,record_value:0x,record_ttl:300}]"
+        );
+        assert_eq!(
+            records().as_prettier(),
+            "[ Record{record_type: Bytes(0x), record_key: Bytes(0x61), record_label: Bytes(0x), record_value: Bytes(0x), \
+             record_ttl: Uint32(300), }, ]"
+        );
+    }
+
+    #[test]
+    fn max_bytes_truncates_long_bytes() {
+        let bytes = Bytes::from(vec![0xab; 40]);
+        let options = PrettyOptions {
+            max_bytes: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(bytes.to_pretty_string(options), "Bytes(0xabababab...(40 bytes))");
+        assert_eq!(
+            bytes.to_pretty_string(PrettyOptions::compact()),
+            alloc::format!("0x{}...(40 bytes)", "ab".repeat(32))
+        );
+
+        // Bytes not longer than max_bytes are printed in full.
+        let bytes = Bytes::from(vec![0xab; 4]);
+        assert_eq!(bytes.to_pretty_string(options), "Bytes(0xabababab)");
+    }
+
+    #[test]
+    fn hex_numbers() {
+        let options = PrettyOptions {
+            hex_numbers: true,
+            ..Default::default()
+        };
+        assert_eq!(Uint32::from(300).to_pretty_string(options), "Uint32(0x12c)");
+        assert_eq!(Uint64::from(0u64).to_pretty_string(options), "Uint64(0x0)");
+        assert_eq!(
+            Uint32::from(300).to_pretty_string(PrettyOptions {
+                hex_numbers: true,
+                ..PrettyOptions::compact()
+            }),
+            "0x12c"
+        );
+    }
+
+    #[test]
+    fn display_adapter() {
+        assert_eq!(
+            alloc::format!("{}", Uint32::from(300).pretty(PrettyOptions::compact())),
+            "300"
+        );
+    }
+
+    fn non_utf8_chars() -> AccountChars {
+        let char = AccountChar::new_builder()
+            .bytes(Bytes::from(&[b'a', 0xff, b'b'][..]))
+            .build();
+        AccountChars::new_builder().push(char).build()
+    }
+
+    #[test]
+    fn non_utf8_text_is_lossy() {
+        assert_eq!(non_utf8_chars().as_prettier(), "AccountChars(a\u{fffd}b)");
+
+        let sub_account = SubAccount::new_builder()
+            .account(non_utf8_chars())
+            .suffix(Bytes::from(&[b'.', 0xfe][..]))
+            .build();
+        let out = sub_account.as_prettier();
+        assert!(out.contains("account: AccountChars(a\u{fffd}b), suffix: .\u{fffd}, "));
+
+        // A truncated multi-byte sequence at the end.
+        let chars = AccountChars::new_builder()
+            .push(
+                AccountChar::new_builder()
+                    .bytes(Bytes::from(&"中".as_bytes()[..2]))
+                    .build(),
+            )
+            .build();
+        assert_eq!(chars.as_prettier(), "AccountChars(\u{fffd})");
+    }
+
+    #[test]
+    fn wrong_length_numbers_are_errors() {
+        let out = Uint64Reader::new_unchecked(&[1, 2, 3]).to_pretty_string(PrettyOptions::default());
+        assert_eq!(out, "Error(Uint64Reader should be 8 bytes, but it is 3 bytes)");
+
+        let out = TimestampReader::new_unchecked(&[1]).to_pretty_string(PrettyOptions::semantic());
+        assert_eq!(out, "Error(TimestampReader should be 8 bytes, but it is 1 bytes)");
+    }
+
+    #[test]
+    fn das_lock_out_point_table_prints_ed25519() {
+        let out_point = OutPoint::new_builder().index(Uint32::from(7)).build();
+        let table = DasLockOutPointTable::new_builder().ed25519(out_point).build();
+        let out = table.to_pretty_string(PrettyOptions::compact());
+
+        assert!(out.starts_with("DasLockOutPointTable{ckb_signall:OutPoint{"));
+        assert!(out.ends_with(
+            ",ed25519:OutPoint{tx_hash:0x0000000000000000000000000000000000000000000000000000000000000000,index:7}}"
+        ));
+    }
+
+    fn account_chars(account: &[u8]) -> AccountChars {
+        let char = AccountChar::new_builder().bytes(Bytes::from(account)).build();
+        AccountChars::new_builder().push(char).build()
+    }
+
+    fn sale_price(price: u64) -> String {
+        AccountSaleCellData::new_builder()
+            .price(Uint64::from(price))
+            .build()
+            .to_pretty_string(PrettyOptions::semantic())
+    }
+
+    #[test]
+    fn semantic_ckb_and_usd_decimals() {
+        assert!(sale_price(12_300_000_000).contains("price: 123 CKB, "));
+        assert!(sale_price(12_345_000_000).contains("price: 123.45 CKB, "));
+        assert!(sale_price(1).contains("price: 0.00000001 CKB, "));
+        assert!(sale_price(0).contains("price: 0 CKB, "));
+
+        let price = PriceConfig::new_builder()
+            .new(Uint64::from(5_000_000u64))
+            .renew(Uint64::from(5_000_001u64))
+            .build();
+        let out = price.to_pretty_string(PrettyOptions::semantic());
+        assert!(out.contains("new: 5 USD, renew: 5.000001 USD, "));
+    }
+
+    #[test]
+    fn semantic_timestamps() {
+        let format = |timestamp: u64| Timestamp::from(timestamp).to_pretty_string(PrettyOptions::semantic());
+        assert_eq!(format(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format(1_700_000_000), "2023-11-14T22:13:20Z");
+        assert_eq!(format(951_825_599), "2000-02-29T11:59:59Z");
+        assert_eq!(format(4_102_444_799), "2099-12-31T23:59:59Z");
+
+        let data = AccountCellData::new_builder()
+            .registered_at(Uint64::from(1_700_000_000u64))
+            .build();
+        assert!(data
+            .to_pretty_string(PrettyOptions::semantic())
+            .contains("registered_at: 2023-11-14T22:13:20Z, "));
+        assert!(data.as_prettier().contains("registered_at: Uint64(1700000000), "));
+    }
+
+    #[test]
+    fn enum_names_of_status() {
+        let data = AccountCellData::new_builder()
+            .status(Uint8::from(2u8))
+            .enable_sub_account(Uint8::from(1u8))
+            .build();
+        let out = data.as_prettier();
+        assert!(out.contains("status: AccountStatus::Auction, "));
+        assert!(out.contains("enable_sub_account: SubAccountEnableStatus::On, "));
+        assert!(data
+            .to_pretty_string(PrettyOptions::compact())
+            .contains("status:Auction,"));
+
+        // Unknown values are printed as they are.
+        let data = AccountCellData::new_builder().status(Uint8::from(9u8)).build();
+        assert!(data.as_prettier().contains("status: Uint8(9), "));
+    }
+
+    #[test]
+    fn account_id_with_readable_account() {
+        let data = AccountCellData::new_builder()
+            .id(AccountId::new_unchecked(vec![0x12; 20].into()))
+            .account(account_chars(b"das"))
+            .build();
+        let out = data.to_pretty_string(PrettyOptions::semantic());
+        assert!(out.contains("id: AccountId(0x1212121212121212121212121212121212121212, das.bit), "));
+        assert!(data
+            .as_prettier()
+            .contains("id: AccountId(0x1212121212121212121212121212121212121212), "));
+
+        let sale = AccountSaleCellData::new_builder()
+            .account(Bytes::from(&b"x.bit"[..]))
+            .build();
+        let options = PrettyOptions {
+            semantic: true,
+            ..PrettyOptions::compact()
+        };
+        assert!(sale
+            .to_pretty_string(options)
+            .contains("account_id:0x0000000000000000000000000000000000000000(x.bit),account:x.bit,"));
+    }
+
+    #[test]
+    fn as_prettier_prints_raw_values() {
+        let price = PriceConfig::new_builder().new(Uint64::from(5_500_000u64)).build();
+        assert_eq!(price.as_prettier(), price.to_pretty_string(PrettyOptions::default()));
+        assert!(price.as_prettier().contains("new: Uint64(5500000)"));
+        assert!(price
+            .to_pretty_string(PrettyOptions::semantic())
+            .contains("new: 5.5 USD"));
+    }
+}
@@ -1,9 +1,11 @@
 mod basic;
 mod cell;
+mod cell_v1;
 
 pub mod packed {
     pub use molecule::prelude::{Byte, ByteReader, Reader};
 
     pub use super::basic::*;
     pub use super::cell::*;
+    pub use super::cell_v1::*;
 }
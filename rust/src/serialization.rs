@@ -0,0 +1,405 @@
+use super::{
+    constants::{AccountStatus, CharSetType, ProposalSliceItemType, SubAccountEnableStatus, SystemStatus},
+    owned::*,
+    schemas::packed::*,
+};
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, marker::PhantomData, str::FromStr};
+use molecule::{hex_string, prelude::*};
+use serde::{
+    de::{self, Deserializer, SeqAccess, Visitor},
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Serialize,
+};
+
+/// The human-readable representation of the fields of owned structs.
+///
+/// Numbers are numbers, Hash, AccountId and other bytes are hex strings with 0x prefix, accounts are UTF-8 strings and
+/// enums are their names. It is used as `#[serde(with = "SerdeValue")]`, so every field type needs to implement it.
+pub trait SerdeValue: Sized {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Wrap a reference to serialize it with SerdeValue.
+struct Ser<'a, T>(&'a T);
+
+impl<'a, T: SerdeValue> Serialize for Ser<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerdeValue::serialize(self.0, serializer)
+    }
+}
+
+/// Wrap a value to deserialize it with SerdeValue.
+struct De<T>(T);
+
+impl<'de, T: SerdeValue> Deserialize<'de> for De<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(De)
+    }
+}
+
+fn decode_hex<E: de::Error>(value: &str) -> Result<Vec<u8>, E> {
+    let hex = value
+        .strip_prefix("0x")
+        .ok_or_else(|| E::custom(format!("hex string should start with 0x: {:?}", value)))?;
+    if hex.len() % 2 != 0 {
+        return Err(E::custom(format!("hex string should have even length: {:?}", value)));
+    }
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| E::custom(format!("invalid hex string: {:?}", value)))
+        })
+        .collect()
+}
+
+struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a hex string with 0x prefix")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        decode_hex(value)
+    }
+}
+
+macro_rules! impl_serde_value_number {
+    ($( $number:ty ),+) => {
+        $(
+            impl SerdeValue for $number {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(self, serializer)
+                }
+
+                fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Deserialize::deserialize(deserializer)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_serde_value_byte_array {
+    ($( $length:literal ),+) => {
+        $(
+            impl SerdeValue for [u8; $length] {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&format!("0x{}", hex_string(self)))
+                }
+
+                fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let bytes = deserializer.deserialize_str(HexVisitor)?;
+                    if bytes.len() != $length {
+                        return Err(de::Error::invalid_length(bytes.len(), &concat!(stringify!($length), " bytes")));
+                    }
+
+                    let mut ret = [0u8; $length];
+                    ret.copy_from_slice(&bytes);
+                    Ok(ret)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_serde_value_enum {
+    ($( $enum_type:ident ),+) => {
+        $(
+            impl SerdeValue for $enum_type {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.name())
+                }
+
+                fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let name = <String as Deserialize>::deserialize(deserializer)?;
+                    $enum_type::from_str(&name).map_err(de::Error::custom)
+                }
+            }
+        )+
+    };
+}
+
+/// The owned structs are serialized as they are.
+macro_rules! impl_serde_value_owned {
+    ($( $owned:ident ),+) => {
+        $(
+            impl SerdeValue for $owned {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(self, serializer)
+                }
+
+                fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Deserialize::deserialize(deserializer)
+                }
+            }
+        )+
+    };
+}
+
+/// Vectors are implemented one by one, because `Vec<u8>` is bytes rather than a list of numbers.
+macro_rules! impl_serde_value_vec {
+    ($( $item:ty ),+) => {
+        $(
+            impl SerdeValue for Vec<$item> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut seq = serializer.serialize_seq(Some(self.len()))?;
+                    for item in self.iter() {
+                        seq.serialize_element(&Ser(item))?;
+                    }
+                    seq.end()
+                }
+
+                fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_seq(SeqVisitor(PhantomData))
+                }
+            }
+        )+
+    };
+}
+
+struct SeqVisitor<T>(PhantomData<T>);
+
+impl<'de, T: SerdeValue> Visitor<'de> for SeqVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut ret = Vec::new();
+        while let Some(De(item)) = seq.next_element::<De<T>>()? {
+            ret.push(item);
+        }
+
+        Ok(ret)
+    }
+}
+
+impl SerdeValue for Vec<u8> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex_string(self)))
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(HexVisitor)
+    }
+}
+
+impl SerdeValue for String {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as Deserialize>::deserialize(deserializer)
+    }
+}
+
+impl<T: SerdeValue> SerdeValue for Option<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(v) => serializer.serialize_some(&Ser(v)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<De<T>>::deserialize(deserializer).map(|v| v.map(|De(v)| v))
+    }
+}
+
+impl_serde_value_number!(u8, u32, u64);
+
+impl_serde_value_byte_array!(20, 32);
+
+impl_serde_value_enum!(
+    SystemStatus,
+    ProposalSliceItemType,
+    AccountStatus,
+    SubAccountEnableStatus,
+    CharSetType
+);
+
+impl_serde_value_owned!(
+    OutPointOwned,
+    ScriptOwned,
+    DataOwned,
+    DataEntityOwned,
+    ActionDataOwned,
+    ConfigCellMainOwned,
+    TypeIdTableOwned,
+    DasLockOutPointTableOwned,
+    ConfigCellAccountOwned,
+    ConfigCellApplyOwned,
+    ConfigCellPriceOwned,
+    DiscountConfigOwned,
+    PriceConfigOwned,
+    ConfigCellProposalOwned,
+    ConfigCellProfitRateOwned,
+    ConfigCellIncomeOwned,
+    ConfigCellReleaseOwned,
+    ConfigCellSecondaryMarketOwned,
+    ConfigCellReverseResolutionOwned,
+    ConfigCellSubAccountOwned,
+    ProposalCellDataOwned,
+    ProposalItemOwned,
+    IncomeCellDataOwned,
+    IncomeRecordOwned,
+    AccountCellDataV1Owned,
+    AccountCellDataV2Owned,
+    AccountCellDataOwned,
+    RecordOwned,
+    AccountSaleCellDataV1Owned,
+    AccountSaleCellDataOwned,
+    AccountAuctionCellDataOwned,
+    PreAccountCellDataOwned,
+    AccountCharOwned,
+    OfferCellDataOwned,
+    SubAccountOwned
+);
+
+impl_serde_value_vec!(
+    Vec<u8>,
+    PriceConfigOwned,
+    Vec<ProposalItemOwned>,
+    ProposalItemOwned,
+    IncomeRecordOwned,
+    RecordOwned,
+    AccountCharOwned
+);
+
+/// Implement serde for the molecule types which have no owned struct, with the same representation as the fields.
+macro_rules! impl_serde_packed {
+    ($( $entity:ident => $owned:ty ),+) => {
+        $(
+            impl Serialize for $entity {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let owned = from_verified_exactly::<$entity, $owned>(self.as_slice())
+                        .map_err(serde::ser::Error::custom)?;
+                    SerdeValue::serialize(&owned, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $entity {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let owned: $owned = SerdeValue::deserialize(deserializer)?;
+                    Ok(OwnedField::<$entity>::to_entity(&owned))
+                }
+            }
+        )+
+    };
+}
+
+impl_serde_packed!(
+    Uint8 => u8,
+    Uint32 => u32,
+    Uint64 => u64,
+    Timestamp => u64,
+    Bytes => Vec<u8>,
+    Hash => [u8; 32],
+    AccountId => [u8; 20],
+    HashOpt => Option<[u8; 32]>,
+    ScriptOpt => Option<ScriptOwned>,
+    DataEntityOpt => Option<DataEntityOwned>,
+    Chars => Vec<Vec<u8>>,
+    PriceConfigList => Vec<PriceConfigOwned>,
+    SliceList => Vec<Vec<ProposalItemOwned>>,
+    SL => Vec<ProposalItemOwned>,
+    IncomeRecords => Vec<IncomeRecordOwned>,
+    Records => Vec<RecordOwned>,
+    AccountChars => Vec<AccountCharOwned>
+);
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::test_util::append_field;
+    use alloc::vec;
+
+    #[test]
+    fn human_readable_representation() {
+        let entity = AccountCellData::from(AccountCellDataOwned {
+            id: [1; 20],
+            account: vec![AccountCharOwned {
+                char_set_name: CharSetType::En,
+                bytes: String::from("a"),
+            }],
+            registered_at: 1,
+            last_transfer_account_at: 2,
+            last_edit_manager_at: 3,
+            last_edit_records_at: 4,
+            status: AccountStatus::Selling,
+            records: vec![],
+            enable_sub_account: SubAccountEnableStatus::On,
+            renew_sub_account_price: 5,
+        });
+        let json = serde_json::to_string(&entity).unwrap();
+
+        assert!(json.contains(&format!("\"id\":\"0x{}\"", "01".repeat(20))));
+        assert!(json.contains("\"bytes\":\"a\""));
+        assert!(json.contains("\"registered_at\":1,"));
+        assert!(json.contains("\"status\":\"selling\""));
+        assert_eq!(
+            serde_json::from_str::<AccountCellData>(&json).unwrap().as_slice(),
+            entity.as_slice()
+        );
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let hash = format!("\"0x{}\"", "ab".repeat(32));
+        let entity: Hash = serde_json::from_str(&hash).unwrap();
+        assert_eq!(serde_json::to_string(&entity).unwrap(), hash);
+
+        let entity = ScriptOpt::new_builder()
+            .set(Some(Script::new_builder().hash_type(Byte::new(1)).build()))
+            .build();
+        let json = serde_json::to_string(&entity).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ScriptOpt>(&json).unwrap().as_slice(),
+            entity.as_slice()
+        );
+        assert_eq!(serde_json::to_string(&ScriptOpt::default()).unwrap(), "null");
+    }
+
+    #[test]
+    fn invalid_documents() {
+        assert!(serde_json::from_str::<Hash>("\"abab\"").is_err());
+        assert!(serde_json::from_str::<Hash>("\"0xabc\"").is_err());
+        assert!(serde_json::from_str::<Hash>("\"0xzz\"").is_err());
+        assert!(serde_json::from_str::<Hash>(&format!("\"0x{}\"", "ab".repeat(31))).is_err());
+        assert!(serde_json::from_str::<ConfigCellApply>(
+            "{\"apply_min_waiting_block_number\":1,\"apply_max_waiting_block_number\":2,\"x\":3}"
+        )
+        .is_err());
+        assert!(serde_json::from_str::<ConfigCellApply>("{\"apply_min_waiting_block_number\":1}").is_err());
+    }
+
+    #[test]
+    fn lossy_data_can_not_be_serialized() {
+        let extended = append_field(ConfigCellApply::default().as_slice(), &[1]);
+        let entity = ConfigCellApply::new_unchecked(extended.into());
+        assert!(serde_json::to_string(&entity).is_err());
+
+        let entity = Records::new_builder()
+            .push(Record::new_unchecked(
+                append_field(Record::default().as_slice(), &[1]).into(),
+            ))
+            .build();
+        assert!(serde_json::to_string(&entity).is_err());
+
+        let entity = OfferCellData::new_builder().account(Bytes::from(&[0xffu8][..])).build();
+        assert!(serde_json::to_string(&entity).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use alloc::vec::Vec;
+use molecule::NUMBER_SIZE;
+
+/// Append a field to the table like a later version of the schema does, the result is only valid in compatible mode.
+// Only the tests of some features use it.
+#[allow(dead_code)]
+pub fn append_field(table: &[u8], field: &[u8]) -> Vec<u8> {
+    let header_size = molecule::unpack_number(&table[NUMBER_SIZE..]) as usize;
+    let field_count = header_size / NUMBER_SIZE - 1;
+    let total_size = table.len() + NUMBER_SIZE + field.len();
+
+    let mut ret = Vec::with_capacity(total_size);
+    ret.extend_from_slice(&molecule::pack_number(total_size as molecule::Number));
+    for i in 0..field_count {
+        let offset = molecule::unpack_number(&table[NUMBER_SIZE * (i + 1)..]) as usize;
+        ret.extend_from_slice(&molecule::pack_number((offset + NUMBER_SIZE) as molecule::Number));
+    }
+    ret.extend_from_slice(&molecule::pack_number((table.len() + NUMBER_SIZE) as molecule::Number));
+    ret.extend_from_slice(&table[header_size..]);
+    ret.extend_from_slice(field);
+    ret
+}
+
+/// Build a table from the bytes of its fields, it is used to build the historical layouts which have fewer fields.
+#[allow(dead_code)]
+pub fn build_table(fields: &[&[u8]]) -> Vec<u8> {
+    let header_size = NUMBER_SIZE * (fields.len() + 1);
+    let total_size = header_size + fields.iter().map(|field| field.len()).sum::<usize>();
+
+    let mut ret = Vec::with_capacity(total_size);
+    ret.extend_from_slice(&molecule::pack_number(total_size as molecule::Number));
+    let mut offset = header_size;
+    for field in fields {
+        ret.extend_from_slice(&molecule::pack_number(offset as molecule::Number));
+        offset += field.len();
+    }
+    for field in fields {
+        ret.extend_from_slice(field);
+    }
+    ret
+}
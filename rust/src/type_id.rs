@@ -0,0 +1,180 @@
+use super::{
+    compatible::TypeIdTableCompatibleReader,
+    constants::{DasCellKind, SCRIPT_HASH_TYPE_TYPE},
+    schemas::packed::*,
+};
+use molecule::prelude::*;
+
+macro_rules! impl_type_id_table {
+    ($( $kind:ident => $field:ident ),+) => {
+        impl<'r> TypeIdTableReader<'r> {
+            /// Get the type ID of the kind of cells, it is the code_hash of their type scripts.
+            pub fn code_hash(&self, kind: DasCellKind) -> HashReader<'r> {
+                match kind {
+                    $(DasCellKind::$kind => self.$field(),)+
+                }
+            }
+
+            /// Find out which kind of DAS cell uses the script as its type script.
+            pub fn classify(&self, script: &ScriptReader) -> Option<DasCellKind> {
+                if u8::from(script.hash_type()) != SCRIPT_HASH_TYPE_TYPE {
+                    return None;
+                }
+
+                let code_hash = script.code_hash();
+                $(
+                    if self.$field().as_slice() == code_hash.as_slice() {
+                        return Some(DasCellKind::$kind);
+                    }
+                )+
+
+                None
+            }
+        }
+
+        impl<'r> TypeIdTableCompatibleReader<'r> {
+            /// Get the type ID of the kind of cells, it is None if the kind does not exist in this layout.
+            pub fn code_hash(&self, kind: DasCellKind) -> Option<HashReader<'r>> {
+                match kind {
+                    $(DasCellKind::$kind => self.$field().into(),)+
+                }
+            }
+
+            /// Find out which kind of DAS cell uses the script as its type script.
+            pub fn classify(&self, script: &ScriptReader) -> Option<DasCellKind> {
+                if u8::from(script.hash_type()) != SCRIPT_HASH_TYPE_TYPE {
+                    return None;
+                }
+
+                let kinds = [$( DasCellKind::$kind ),+];
+                kinds.iter().copied().find(|kind| match self.code_hash(*kind) {
+                    Some(code_hash) => code_hash.as_slice() == script.code_hash().as_slice(),
+                    None => false,
+                })
+            }
+        }
+    };
+}
+
+impl_type_id_table!(
+    AccountCell => account_cell,
+    ApplyRegisterCell => apply_register_cell,
+    BalanceCell => balance_cell,
+    IncomeCell => income_cell,
+    PreAccountCell => pre_account_cell,
+    ProposalCell => proposal_cell,
+    AccountSaleCell => account_sale_cell,
+    AccountAuctionCell => account_auction_cell,
+    OfferCell => offer_cell,
+    ReverseRecordCell => reverse_record_cell,
+    SubAccountCell => sub_account_cell
+);
+
+impl TypeIdTable {
+    pub fn code_hash(&self, kind: DasCellKind) -> Hash {
+        self.as_reader().code_hash(kind).to_entity()
+    }
+
+    pub fn classify(&self, script: &ScriptReader) -> Option<DasCellKind> {
+        self.as_reader().classify(script)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compatible::DecodeMode, test_util::build_table};
+    use alloc::vec::Vec;
+
+    // In the order of the fields of TypeIdTable.
+    const KINDS: [DasCellKind; 11] = [
+        DasCellKind::AccountCell,
+        DasCellKind::ApplyRegisterCell,
+        DasCellKind::BalanceCell,
+        DasCellKind::IncomeCell,
+        DasCellKind::PreAccountCell,
+        DasCellKind::ProposalCell,
+        DasCellKind::AccountSaleCell,
+        DasCellKind::AccountAuctionCell,
+        DasCellKind::OfferCell,
+        DasCellKind::ReverseRecordCell,
+        DasCellKind::SubAccountCell,
+    ];
+
+    // Every field has a different hash, the hash of the field at index is filled with index + 1.
+    fn hashes(count: usize) -> Vec<Hash> {
+        (0..count).map(|i| Hash::from([i as u8 + 1; 32])).collect()
+    }
+
+    fn type_id_table() -> TypeIdTable {
+        let hashes = hashes(KINDS.len());
+        let fields: Vec<&[u8]> = hashes.iter().map(|v| v.as_slice()).collect();
+        TypeIdTable::new_unchecked(build_table(&fields).into())
+    }
+
+    fn script(code_hash: Hash, hash_type: u8) -> Script {
+        Script::new_builder()
+            .code_hash(code_hash)
+            .hash_type(Byte::new(hash_type))
+            .build()
+    }
+
+    #[test]
+    fn classify_every_kind() {
+        let table = type_id_table();
+        assert!(TypeIdTableReader::verify(table.as_slice(), false).is_ok());
+
+        for (kind, code_hash) in KINDS.iter().zip(hashes(KINDS.len())) {
+            assert_eq!(table.code_hash(*kind).as_slice(), code_hash.as_slice());
+
+            let script = script(code_hash, SCRIPT_HASH_TYPE_TYPE);
+            assert_eq!(table.classify(&script.as_reader()), Some(*kind));
+            assert_eq!(table.as_reader().classify(&script.as_reader()), Some(*kind));
+        }
+    }
+
+    #[test]
+    fn classify_requires_hash_type_type() {
+        let table = type_id_table();
+        let code_hash = table.code_hash(DasCellKind::AccountCell);
+
+        // The data hash_types reference the code by its data hash, even the same hash is not the type ID.
+        for hash_type in [0u8, 2, 4].iter() {
+            let script = script(code_hash.clone(), *hash_type);
+            assert_eq!(table.classify(&script.as_reader()), None);
+        }
+    }
+
+    #[test]
+    fn classify_unknown_hash() {
+        let table = type_id_table();
+        let script = script(Hash::from([0xffu8; 32]), SCRIPT_HASH_TYPE_TYPE);
+        assert_eq!(table.classify(&script.as_reader()), None);
+    }
+
+    #[test]
+    fn compatible_reader_of_historical_layout() {
+        // The layout before account_sale_cell was appended.
+        let hashes = hashes(6);
+        let fields: Vec<&[u8]> = hashes.iter().map(|v| v.as_slice()).collect();
+        let data = build_table(&fields);
+        let table = TypeIdTableCompatibleReader::from_slice(&data, DecodeMode::Strict).unwrap();
+
+        for (index, kind) in KINDS.iter().enumerate() {
+            let script = script(Hash::from([index as u8 + 1; 32]), SCRIPT_HASH_TYPE_TYPE);
+            if index < 6 {
+                assert_eq!(
+                    table.code_hash(*kind).map(|v| v.as_slice()),
+                    Some(hashes[index].as_slice())
+                );
+                assert_eq!(table.classify(&script.as_reader()), Some(*kind));
+            } else {
+                assert!(table.code_hash(*kind).is_none());
+                assert_eq!(table.classify(&script.as_reader()), None);
+            }
+        }
+
+        let script = script(hashes[0].clone(), 0);
+        assert_eq!(table.classify(&script.as_reader()), None);
+    }
+}
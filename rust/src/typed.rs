@@ -0,0 +1,64 @@
+#[cfg(feature = "compatible")]
+use super::compatible::ConfigCellMainCompatibleReader;
+use super::{
+    constants::{AccountStatus, CharSetType, ProposalSliceItemType, SubAccountEnableStatus, SystemStatus},
+    error::DasTypesError,
+    schemas::packed::*,
+};
+use core::convert::TryFrom;
+
+/// Add accessors which decode Uint8 and Uint32 fields into their enums, they return an error for unknown values.
+macro_rules! impl_typed_accessor {
+    ($( $entity:ident, $reader:ident { $( $typed:ident: $field:ident -> $enum_type:ident ),+ } );+) => {
+        $(
+            impl $entity {
+                $(
+                    pub fn $typed(&self) -> Result<$enum_type, DasTypesError> {
+                        $enum_type::try_from(self.as_reader().$field())
+                    }
+                )+
+            }
+
+            impl<'r> $reader<'r> {
+                $(
+                    pub fn $typed(&self) -> Result<$enum_type, DasTypesError> {
+                        $enum_type::try_from(self.$field())
+                    }
+                )+
+            }
+        )+
+    };
+}
+
+impl_typed_accessor!(
+    ConfigCellMain, ConfigCellMainReader {
+        system_status: status -> SystemStatus
+    };
+    ProposalItem, ProposalItemReader {
+        item_type_typed: item_type -> ProposalSliceItemType
+    };
+    AccountCellDataV1, AccountCellDataV1Reader {
+        status_typed: status -> AccountStatus
+    };
+    AccountCellDataV2, AccountCellDataV2Reader {
+        status_typed: status -> AccountStatus
+    };
+    AccountCellData, AccountCellDataReader {
+        status_typed: status -> AccountStatus,
+        enable_sub_account_typed: enable_sub_account -> SubAccountEnableStatus
+    };
+    SubAccount, SubAccountReader {
+        status_typed: status -> AccountStatus,
+        enable_sub_account_typed: enable_sub_account -> SubAccountEnableStatus
+    };
+    AccountChar, AccountCharReader {
+        char_set_name_typed: char_set_name -> CharSetType
+    }
+);
+
+#[cfg(feature = "compatible")]
+impl<'r> ConfigCellMainCompatibleReader<'r> {
+    pub fn system_status(&self) -> Result<SystemStatus, DasTypesError> {
+        SystemStatus::try_from(self.status())
+    }
+}
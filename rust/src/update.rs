@@ -0,0 +1,527 @@
+use super::{
+    constants::{AccountStatus, CharSetType, ProposalSliceItemType, SubAccountEnableStatus, SystemStatus},
+    schemas::packed::*,
+    util::verify_offsets,
+};
+use alloc::vec::Vec;
+use molecule::{bytes, prelude::*};
+
+/// Replace the field at the index of the table, the other bytes are kept as they are, including the extra fields of
+/// compatible mode. It returns None if the header of the table is broken.
+fn replace_field(name: &str, table: &[u8], index: usize, field: &[u8]) -> Option<bytes::Bytes> {
+    let offsets = verify_offsets(name, table).ok()?;
+    if index + 1 >= offsets.len() {
+        return None;
+    }
+
+    let total_size = table.len();
+    let header_size = offsets[0];
+    let (start, end) = (offsets[index], offsets[index + 1]);
+    let new_size = total_size - (end - start) + field.len();
+    let mut ret = Vec::with_capacity(new_size);
+    ret.extend_from_slice(&molecule::pack_number(new_size as molecule::Number));
+    for (i, offset) in offsets[..offsets.len() - 1].iter().enumerate() {
+        let offset = if i > index {
+            offset - (end - start) + field.len()
+        } else {
+            *offset
+        };
+        ret.extend_from_slice(&molecule::pack_number(offset as molecule::Number));
+    }
+    ret.extend_from_slice(&table[header_size..start]);
+    ret.extend_from_slice(field);
+    ret.extend_from_slice(&table[end..]);
+
+    Some(ret.into())
+}
+
+/// Generate `with_*` methods which return a new entity with one field replaced, and the same `with_*` methods on
+/// builders.
+///
+/// The fields are `method => field: FieldType` or `method => field: FieldType as NativeType`, the latter takes the
+/// native type and converts it into the field type with From, so a status only accepts its enum rather than any u8. The
+/// former takes the field type on entities, and anything can be converted into it on builders, like a `Vec<Record>` for
+/// Records. They must be listed in the order of the schema, because the position of a field is its index in the table.
+/// Only the bytes of the field are replaced, all other bytes are preserved exactly.
+macro_rules! impl_with_fields {
+    ($( $entity:ident, $builder:ident { $( $fields:tt )+ } );+) => {
+        $(impl_with_fields!(@field $entity, $builder, 0, $( $fields )+);)+
+    };
+    (@field $entity:ident, $builder:ident, $index:expr,
+        $method:ident => $field:ident: $field_type:ident as $native:ty $(, $( $rest:tt )+)?
+    ) => {
+        impl_with_fields!(@entity $entity, $index, $method, $field, $field_type, $native);
+
+        impl $builder {
+            pub fn $method(self, value: $native) -> Self {
+                self.$field($field_type::from(value))
+            }
+        }
+
+        $(impl_with_fields!(@field $entity, $builder, $index + 1, $( $rest )+);)?
+    };
+    (@field $entity:ident, $builder:ident, $index:expr,
+        $method:ident => $field:ident: $field_type:ident $(, $( $rest:tt )+)?
+    ) => {
+        impl_with_fields!(@entity $entity, $index, $method, $field, $field_type, $field_type);
+
+        impl $builder {
+            pub fn $method(self, value: impl Into<$field_type>) -> Self {
+                self.$field(value.into())
+            }
+        }
+
+        $(impl_with_fields!(@field $entity, $builder, $index + 1, $( $rest )+);)?
+    };
+    (@entity $entity:ident, $index:expr, $method:ident, $field:ident, $field_type:ident, $native:ty) => {
+        impl $entity {
+            pub fn $method(&self, value: $native) -> Self {
+                let field = $field_type::from(value);
+                match replace_field(Self::NAME, self.as_slice(), $index, field.as_slice()) {
+                    Some(data) => Self::new_unchecked(data),
+                    // The entity is not a valid table, rebuild it like other accessors of the entity do.
+                    None => self.clone().as_builder().$field(field).build(),
+                }
+            }
+        }
+    };
+}
+
+impl_with_fields!(
+    Script, ScriptBuilder {
+        with_code_hash => code_hash: Hash as [u8; 32],
+        with_hash_type => hash_type: Byte as u8,
+        with_args => args: Bytes as &[u8]
+    };
+    Data, DataBuilder {
+        with_dep => dep: DataEntityOpt,
+        with_old => old: DataEntityOpt,
+        with_new => new: DataEntityOpt
+    };
+    DataEntity, DataEntityBuilder {
+        with_index => index: Uint32 as u32,
+        with_version => version: Uint32 as u32,
+        with_entity => entity: Bytes as &[u8]
+    };
+    ActionData, ActionDataBuilder {
+        with_action => action: Bytes as &[u8],
+        with_params => params: Bytes as &[u8]
+    };
+    ConfigCellMain, ConfigCellMainBuilder {
+        with_status => status: Uint8 as SystemStatus,
+        with_type_id_table => type_id_table: TypeIdTable,
+        with_das_lock_out_point_table => das_lock_out_point_table: DasLockOutPointTable
+    };
+    TypeIdTable, TypeIdTableBuilder {
+        with_account_cell => account_cell: Hash as [u8; 32],
+        with_apply_register_cell => apply_register_cell: Hash as [u8; 32],
+        with_balance_cell => balance_cell: Hash as [u8; 32],
+        with_income_cell => income_cell: Hash as [u8; 32],
+        with_pre_account_cell => pre_account_cell: Hash as [u8; 32],
+        with_proposal_cell => proposal_cell: Hash as [u8; 32],
+        with_account_sale_cell => account_sale_cell: Hash as [u8; 32],
+        with_account_auction_cell => account_auction_cell: Hash as [u8; 32],
+        with_offer_cell => offer_cell: Hash as [u8; 32],
+        with_reverse_record_cell => reverse_record_cell: Hash as [u8; 32],
+        with_sub_account_cell => sub_account_cell: Hash as [u8; 32]
+    };
+    DasLockOutPointTable, DasLockOutPointTableBuilder {
+        with_ckb_signall => ckb_signall: OutPoint,
+        with_ckb_multisign => ckb_multisign: OutPoint,
+        with_ckb_anyone_can_pay => ckb_anyone_can_pay: OutPoint,
+        with_eth => eth: OutPoint,
+        with_tron => tron: OutPoint,
+        with_ed25519 => ed25519: OutPoint
+    };
+    ConfigCellAccount, ConfigCellAccountBuilder {
+        with_max_length => max_length: Uint32 as u32,
+        with_basic_capacity => basic_capacity: Uint64 as u64,
+        with_prepared_fee_capacity => prepared_fee_capacity: Uint64 as u64,
+        with_expiration_grace_period => expiration_grace_period: Uint32 as u32,
+        with_record_min_ttl => record_min_ttl: Uint32 as u32,
+        with_record_size_limit => record_size_limit: Uint32 as u32,
+        with_transfer_account_fee => transfer_account_fee: Uint64 as u64,
+        with_edit_manager_fee => edit_manager_fee: Uint64 as u64,
+        with_edit_records_fee => edit_records_fee: Uint64 as u64,
+        with_common_fee => common_fee: Uint64 as u64,
+        with_transfer_account_throttle => transfer_account_throttle: Uint32 as u32,
+        with_edit_manager_throttle => edit_manager_throttle: Uint32 as u32,
+        with_edit_records_throttle => edit_records_throttle: Uint32 as u32,
+        with_common_throttle => common_throttle: Uint32 as u32
+    };
+    ConfigCellApply, ConfigCellApplyBuilder {
+        with_apply_min_waiting_block_number => apply_min_waiting_block_number: Uint32 as u32,
+        with_apply_max_waiting_block_number => apply_max_waiting_block_number: Uint32 as u32
+    };
+    ConfigCellPrice, ConfigCellPriceBuilder {
+        with_discount => discount: DiscountConfig,
+        with_prices => prices: PriceConfigList
+    };
+    DiscountConfig, DiscountConfigBuilder {
+        with_invited_discount => invited_discount: Uint32 as u32
+    };
+    PriceConfig, PriceConfigBuilder {
+        with_length => length: Uint8 as u8,
+        with_new => new: Uint64 as u64,
+        with_renew => renew: Uint64 as u64
+    };
+    ConfigCellProposal, ConfigCellProposalBuilder {
+        with_proposal_min_confirm_interval => proposal_min_confirm_interval: Uint8 as u8,
+        with_proposal_min_extend_interval => proposal_min_extend_interval: Uint8 as u8,
+        with_proposal_min_recycle_interval => proposal_min_recycle_interval: Uint8 as u8,
+        with_proposal_max_account_affect => proposal_max_account_affect: Uint32 as u32,
+        with_proposal_max_pre_account_contain => proposal_max_pre_account_contain: Uint32 as u32
+    };
+    ConfigCellProfitRate, ConfigCellProfitRateBuilder {
+        with_inviter => inviter: Uint32 as u32,
+        with_channel => channel: Uint32 as u32,
+        with_proposal_create => proposal_create: Uint32 as u32,
+        with_proposal_confirm => proposal_confirm: Uint32 as u32,
+        with_income_consolidate => income_consolidate: Uint32 as u32,
+        with_sale_buyer_inviter => sale_buyer_inviter: Uint32 as u32,
+        with_sale_buyer_channel => sale_buyer_channel: Uint32 as u32,
+        with_sale_das => sale_das: Uint32 as u32,
+        with_auction_bidder_inviter => auction_bidder_inviter: Uint32 as u32,
+        with_auction_bidder_channel => auction_bidder_channel: Uint32 as u32,
+        with_auction_das => auction_das: Uint32 as u32,
+        with_auction_prev_bidder => auction_prev_bidder: Uint32 as u32
+    };
+    ConfigCellIncome, ConfigCellIncomeBuilder {
+        with_basic_capacity => basic_capacity: Uint64 as u64,
+        with_max_records => max_records: Uint32 as u32,
+        with_min_transfer_capacity => min_transfer_capacity: Uint64 as u64
+    };
+    ConfigCellRelease, ConfigCellReleaseBuilder {
+        with_lucky_number => lucky_number: Uint32 as u32
+    };
+    ConfigCellSecondaryMarket, ConfigCellSecondaryMarketBuilder {
+        with_common_fee => common_fee: Uint64 as u64,
+        with_sale_min_price => sale_min_price: Uint64 as u64,
+        with_sale_expiration_limit => sale_expiration_limit: Uint32 as u32,
+        with_sale_description_bytes_limit => sale_description_bytes_limit: Uint32 as u32,
+        with_sale_cell_basic_capacity => sale_cell_basic_capacity: Uint64 as u64,
+        with_sale_cell_prepared_fee_capacity => sale_cell_prepared_fee_capacity: Uint64 as u64,
+        with_auction_max_extendable_duration => auction_max_extendable_duration: Uint32 as u32,
+        with_auction_duration_increment_each_bid => auction_duration_increment_each_bid: Uint32 as u32,
+        with_auction_min_opening_price => auction_min_opening_price: Uint64 as u64,
+        with_auction_min_increment_rate_each_bid => auction_min_increment_rate_each_bid: Uint32 as u32,
+        with_auction_description_bytes_limit => auction_description_bytes_limit: Uint32 as u32,
+        with_auction_cell_basic_capacity => auction_cell_basic_capacity: Uint64 as u64,
+        with_auction_cell_prepared_fee_capacity => auction_cell_prepared_fee_capacity: Uint64 as u64,
+        with_offer_min_price => offer_min_price: Uint64 as u64,
+        with_offer_cell_basic_capacity => offer_cell_basic_capacity: Uint64 as u64,
+        with_offer_cell_prepared_fee_capacity => offer_cell_prepared_fee_capacity: Uint64 as u64,
+        with_offer_message_bytes_limit => offer_message_bytes_limit: Uint32 as u32
+    };
+    ConfigCellReverseResolution, ConfigCellReverseResolutionBuilder {
+        with_record_basic_capacity => record_basic_capacity: Uint64 as u64,
+        with_record_prepared_fee_capacity => record_prepared_fee_capacity: Uint64 as u64,
+        with_common_fee => common_fee: Uint64 as u64
+    };
+    ConfigCellSubAccount, ConfigCellSubAccountBuilder {
+        with_basic_capacity => basic_capacity: Uint64 as u64,
+        with_prepared_fee_capacity => prepared_fee_capacity: Uint64 as u64,
+        with_new_sub_account_price => new_sub_account_price: Uint64 as u64,
+        with_renew_sub_account_price => renew_sub_account_price: Uint64 as u64,
+        with_common_fee => common_fee: Uint64 as u64,
+        with_create_fee => create_fee: Uint64 as u64,
+        with_edit_fee => edit_fee: Uint64 as u64,
+        with_renew_fee => renew_fee: Uint64 as u64,
+        with_recycle_fee => recycle_fee: Uint64 as u64
+    };
+    ProposalCellData, ProposalCellDataBuilder {
+        with_proposer_lock => proposer_lock: Script,
+        with_created_at_height => created_at_height: Uint64 as u64,
+        with_slices => slices: SliceList
+    };
+    ProposalItem, ProposalItemBuilder {
+        with_account_id => account_id: AccountId,
+        with_item_type => item_type: Uint8 as ProposalSliceItemType,
+        with_next => next: AccountId
+    };
+    IncomeCellData, IncomeCellDataBuilder {
+        with_creator => creator: Script,
+        with_records => records: IncomeRecords
+    };
+    IncomeRecord, IncomeRecordBuilder {
+        with_belong_to => belong_to: Script,
+        with_capacity => capacity: Uint64 as u64
+    };
+    AccountCellDataV1, AccountCellDataV1Builder {
+        with_id => id: AccountId,
+        with_account => account: AccountChars,
+        with_registered_at => registered_at: Uint64 as u64,
+        with_updated_at => updated_at: Uint64 as u64,
+        with_status => status: Uint8 as AccountStatus,
+        with_records => records: Records
+    };
+    AccountCellDataV2, AccountCellDataV2Builder {
+        with_id => id: AccountId,
+        with_account => account: AccountChars,
+        with_registered_at => registered_at: Uint64 as u64,
+        with_last_transfer_account_at => last_transfer_account_at: Uint64 as u64,
+        with_last_edit_manager_at => last_edit_manager_at: Uint64 as u64,
+        with_last_edit_records_at => last_edit_records_at: Uint64 as u64,
+        with_status => status: Uint8 as AccountStatus,
+        with_records => records: Records
+    };
+    AccountCellData, AccountCellDataBuilder {
+        with_id => id: AccountId,
+        with_account => account: AccountChars,
+        with_registered_at => registered_at: Uint64 as u64,
+        with_last_transfer_account_at => last_transfer_account_at: Uint64 as u64,
+        with_last_edit_manager_at => last_edit_manager_at: Uint64 as u64,
+        with_last_edit_records_at => last_edit_records_at: Uint64 as u64,
+        with_status => status: Uint8 as AccountStatus,
+        with_records => records: Records,
+        with_enable_sub_account => enable_sub_account: Uint8 as SubAccountEnableStatus,
+        with_renew_sub_account_price => renew_sub_account_price: Uint64 as u64
+    };
+    Record, RecordBuilder {
+        with_record_type => record_type: Bytes as &[u8],
+        with_record_key => record_key: Bytes as &[u8],
+        with_record_label => record_label: Bytes as &[u8],
+        with_record_value => record_value: Bytes as &[u8],
+        with_record_ttl => record_ttl: Uint32 as u32
+    };
+    AccountSaleCellDataV1, AccountSaleCellDataV1Builder {
+        with_account_id => account_id: AccountId,
+        with_account => account: Bytes as &str,
+        with_price => price: Uint64 as u64,
+        with_description => description: Bytes as &[u8],
+        with_started_at => started_at: Uint64 as u64
+    };
+    AccountSaleCellData, AccountSaleCellDataBuilder {
+        with_account_id => account_id: AccountId,
+        with_account => account: Bytes as &str,
+        with_price => price: Uint64 as u64,
+        with_description => description: Bytes as &[u8],
+        with_started_at => started_at: Uint64 as u64,
+        with_buyer_inviter_profit_rate => buyer_inviter_profit_rate: Uint32 as u32
+    };
+    AccountAuctionCellData, AccountAuctionCellDataBuilder {
+        with_account_id => account_id: AccountId,
+        with_account => account: Bytes as &str,
+        with_description => description: Bytes as &[u8],
+        with_opening_price => opening_price: Uint64 as u64,
+        with_increment_rate_each_bid => increment_rate_each_bid: Uint32 as u32,
+        with_started_at => started_at: Uint64 as u64,
+        with_ended_at => ended_at: Uint64 as u64,
+        with_current_bidder_lock => current_bidder_lock: Script,
+        with_current_bid_price => current_bid_price: Uint64 as u64,
+        with_prev_bidder_profit_rate => prev_bidder_profit_rate: Uint32 as u32
+    };
+    PreAccountCellData, PreAccountCellDataBuilder {
+        with_account => account: AccountChars,
+        with_refund_lock => refund_lock: Script,
+        with_owner_lock_args => owner_lock_args: Bytes as &[u8],
+        with_inviter_id => inviter_id: Bytes as &[u8],
+        with_inviter_lock => inviter_lock: ScriptOpt,
+        with_channel_lock => channel_lock: ScriptOpt,
+        with_price => price: PriceConfig,
+        with_quote => quote: Uint64 as u64,
+        with_invited_discount => invited_discount: Uint32 as u32,
+        with_created_at => created_at: Uint64 as u64
+    };
+    AccountChar, AccountCharBuilder {
+        with_char_set_name => char_set_name: Uint32 as CharSetType,
+        with_bytes => bytes: Bytes as &[u8]
+    };
+    OfferCellData, OfferCellDataBuilder {
+        with_account => account: Bytes as &str,
+        with_price => price: Uint64 as u64,
+        with_message => message: Bytes as &[u8],
+        with_inviter_lock => inviter_lock: Script,
+        with_channel_lock => channel_lock: Script
+    };
+    SubAccount, SubAccountBuilder {
+        with_lock => lock: Script,
+        with_id => id: AccountId,
+        with_account => account: AccountChars,
+        with_suffix => suffix: Bytes as &str,
+        with_registered_at => registered_at: Uint64 as u64,
+        with_expired_at => expired_at: Uint64 as u64,
+        with_status => status: Uint8 as AccountStatus,
+        with_records => records: Records,
+        with_nonce => nonce: Uint64 as u64,
+        with_enable_sub_account => enable_sub_account: Uint8 as SubAccountEnableStatus,
+        with_renew_sub_account_price => renew_sub_account_price: Uint64 as u64
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::append_field;
+    use alloc::vec;
+
+    #[test]
+    fn builders_take_native_values() {
+        let data = AccountSaleCellData::new_builder()
+            .with_account_id([1u8; 20])
+            .with_account("das.bit")
+            .with_price(100)
+            .with_description(b"hi")
+            .with_started_at(7)
+            .with_buyer_inviter_profit_rate(100)
+            .build();
+        let expected = AccountSaleCellData::new_builder()
+            .account_id(AccountId::from([1u8; 20]))
+            .account(Bytes::from(&b"das.bit"[..]))
+            .price(Uint64::from(100))
+            .description(Bytes::from(&b"hi"[..]))
+            .started_at(Uint64::from(7))
+            .buyer_inviter_profit_rate(Uint32::from(100))
+            .build();
+
+        assert_eq!(data.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn builders_take_enums_for_typed_fields() {
+        let data = AccountCellData::new_builder()
+            .with_status(AccountStatus::Selling)
+            .with_enable_sub_account(SubAccountEnableStatus::On)
+            .with_records(vec![Record::new_builder()
+                .with_record_key(b"a")
+                .with_record_ttl(300)
+                .build()])
+            .build();
+
+        assert_eq!(data.status_typed().unwrap(), AccountStatus::Selling);
+        assert_eq!(data.enable_sub_account_typed().unwrap(), SubAccountEnableStatus::On);
+        assert_eq!(data.records().len(), 1);
+        assert_eq!(u32::from(data.records().get(0).unwrap().record_ttl()), 300);
+    }
+
+    #[test]
+    fn builders_wrap_options() {
+        let lock = Script::new_builder().with_args(b"a").build();
+        let data = PreAccountCellData::new_builder()
+            .with_inviter_lock(lock.clone())
+            .build();
+
+        assert_eq!(
+            data.inviter_lock().to_opt().map(|v| v.as_bytes()),
+            Some(lock.as_bytes())
+        );
+        assert!(data.channel_lock().to_opt().is_none());
+    }
+
+    fn record(key: &[u8], value: &[u8]) -> Record {
+        Record::new_builder()
+            .record_type(Bytes::from(&b"address"[..]))
+            .record_key(Bytes::from(key))
+            .record_label(Bytes::from(&b"label"[..]))
+            .record_value(Bytes::from(value))
+            .record_ttl(Uint32::from(300))
+            .build()
+    }
+
+    #[test]
+    fn replace_first_middle_and_last_fields() {
+        let old = record(b"a", b"v");
+        let first = Bytes::from(&b"email"[..]);
+        let ttl = Uint32::from(60);
+
+        let data = replace_field(RecordReader::NAME, old.as_slice(), 0, first.as_slice()).unwrap();
+        let expected = old.clone().as_builder().record_type(first).build();
+        assert_eq!(data, expected.as_bytes());
+
+        let data = replace_field(
+            RecordReader::NAME,
+            old.as_slice(),
+            2,
+            Bytes::from(&b"LABEL"[..]).as_slice(),
+        )
+        .unwrap();
+        let expected = old
+            .clone()
+            .as_builder()
+            .record_label(Bytes::from(&b"LABEL"[..]))
+            .build();
+        assert_eq!(data, expected.as_bytes());
+
+        let data = replace_field(RecordReader::NAME, old.as_slice(), 4, ttl.as_slice()).unwrap();
+        let expected = old.as_builder().record_ttl(ttl).build();
+        assert_eq!(data, expected.as_bytes());
+    }
+
+    #[test]
+    fn replace_field_with_different_size_updates_offsets() {
+        let old = record(b"a", b"v");
+        for key in [&b""[..], &b"a much longer record key"[..]].iter() {
+            let data = replace_field(RecordReader::NAME, old.as_slice(), 1, Bytes::from(*key).as_slice()).unwrap();
+            let expected = record(key, b"v");
+            assert_eq!(data, expected.as_bytes());
+            assert!(RecordReader::verify(&data, false).is_ok());
+        }
+    }
+
+    #[test]
+    fn replace_field_preserves_extra_fields() {
+        let old = append_field(record(b"a", b"v").as_slice(), &[1, 2, 3]);
+        let data = replace_field(RecordReader::NAME, &old, 3, Bytes::from(&b"value"[..]).as_slice()).unwrap();
+
+        let expected = append_field(record(b"a", b"value").as_slice(), &[1, 2, 3]);
+        assert_eq!(&data[..], &expected[..]);
+        assert!(RecordReader::verify(&data, true).is_ok());
+
+        // The last field of the current schema ends before the extra fields.
+        let data = replace_field(RecordReader::NAME, &old, 4, Uint32::from(60).as_slice()).unwrap();
+        let reader = RecordReader::from_compatible_slice(&data).unwrap();
+        assert_eq!(u32::from(reader.record_ttl().to_entity()), 60);
+        assert_eq!(&data[data.len() - 3..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn replace_field_of_broken_table() {
+        let old = record(b"a", b"v");
+        let field = Bytes::default();
+
+        assert!(replace_field(RecordReader::NAME, &[], 0, field.as_slice()).is_none());
+        assert!(replace_field(
+            RecordReader::NAME,
+            &old.as_slice()[..old.as_slice().len() - 1],
+            0,
+            field.as_slice()
+        )
+        .is_none());
+        // The table has only 5 fields.
+        assert!(replace_field(RecordReader::NAME, old.as_slice(), 5, field.as_slice()).is_none());
+
+        let mut broken = old.as_slice().to_vec();
+        broken[4] = 0xff;
+        assert!(replace_field(RecordReader::NAME, &broken, 0, field.as_slice()).is_none());
+
+        // The header size is not a multiple of the offset size.
+        let mut broken = old.as_slice().to_vec();
+        broken[4] = 22;
+        assert!(replace_field(RecordReader::NAME, &broken, 0, field.as_slice()).is_none());
+
+        // The offset of record_label is less than the offset of record_key.
+        let offset_at = |i: usize| 4 * (i + 1);
+        let mut broken = old.as_slice().to_vec();
+        let key_offset = broken[offset_at(1)];
+        broken[offset_at(2)] = key_offset - 1;
+        for index in 0..5 {
+            assert!(replace_field(RecordReader::NAME, &broken, index, field.as_slice()).is_none());
+        }
+
+        // The offset of record_key points inside the header.
+        let mut broken = old.as_slice().to_vec();
+        broken[offset_at(1)] = 8;
+        for index in 0..5 {
+            assert!(replace_field(RecordReader::NAME, &broken, index, field.as_slice()).is_none());
+        }
+    }
+
+    #[test]
+    fn with_methods_preserve_extra_fields() {
+        let old = append_field(record(b"a", b"v").as_slice(), &[1, 2, 3]);
+        let data = Record::new_unchecked(old.into()).with_record_key(b"b");
+
+        let expected = append_field(record(b"b", b"v").as_slice(), &[1, 2, 3]);
+        assert_eq!(data.as_slice(), &expected[..]);
+    }
+}
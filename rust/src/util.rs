@@ -1,6 +1,16 @@
-use super::constants::*;
+use super::{constants::*, error::DasTypesError};
+use alloc::vec::Vec;
+#[cfg(any(feature = "compatible", feature = "update", feature = "verify"))]
+use alloc::{borrow::ToOwned, vec};
+#[cfg(feature = "hash")]
+use blake2b_rs::Blake2bBuilder;
 use core::convert::TryFrom;
 use molecule::prelude::*;
+#[cfg(any(feature = "compatible", feature = "update", feature = "verify"))]
+use molecule::{
+    error::{VerificationError, VerificationResult},
+    NUMBER_SIZE,
+};
 
 pub use molecule::hex_string;
 
@@ -13,17 +23,174 @@ pub fn is_reader_eq<'a, T: Reader<'a>>(a: T, b: T) -> bool {
 }
 
 pub fn data_type_to_char_set(data_type: DataType) -> CharSetType {
-    CharSetType::try_from(data_type as u32 - 100000).unwrap()
+    try_data_type_to_char_set(data_type).expect("The DataType should be a char set.")
+}
+
+pub fn try_data_type_to_char_set(data_type: DataType) -> Result<CharSetType, DasTypesError> {
+    if !data_type.is_char_set() {
+        return Err(DasTypesError::DataTypeNotMatch(data_type, DataTypeCategory::CharSet));
+    }
+
+    CharSetType::try_from(data_type as u32 - DataType::ConfigCellCharSetEmoji as u32)
 }
 
 pub fn char_set_to_data_type(char_set: CharSetType) -> DataType {
-    DataType::try_from(char_set as u32 + 100000).unwrap()
+    try_char_set_to_data_type(char_set).expect("Every CharSetType should have a DataType.")
+}
+
+pub fn try_char_set_to_data_type(char_set: CharSetType) -> Result<DataType, DasTypesError> {
+    DataType::try_from(char_set as u32 + DataType::ConfigCellCharSetEmoji as u32)
 }
 
 pub fn data_type_to_preserved_accounts_group(data_type: DataType) -> usize {
-    data_type as u32 as usize - 10000
+    try_data_type_to_preserved_accounts_group(data_type).expect("The DataType should be a preserved account group.")
+}
+
+pub fn try_data_type_to_preserved_accounts_group(data_type: DataType) -> Result<usize, DasTypesError> {
+    if !data_type.is_preserved_account() {
+        return Err(DasTypesError::DataTypeNotMatch(
+            data_type,
+            DataTypeCategory::PreservedAccount,
+        ));
+    }
+
+    Ok((data_type as u32 - DataType::ConfigCellPreservedAccount00 as u32) as usize)
 }
 
 pub fn preserved_accounts_group_to_data_type(group: usize) -> DataType {
-    DataType::try_from(group as u32 + 10000).unwrap()
+    try_preserved_accounts_group_to_data_type(group).expect("The group of preserved accounts should be less than 20.")
+}
+
+pub fn try_preserved_accounts_group_to_data_type(group: usize) -> Result<DataType, DasTypesError> {
+    if group >= PRESERVED_ACCOUNT_CELL_COUNT as usize {
+        return Err(DasTypesError::OutOfRange("PreservedAccount", group));
+    }
+
+    DataType::try_from(group as u32 + DataType::ConfigCellPreservedAccount00 as u32)
+}
+
+#[cfg(feature = "hash")]
+pub fn blake2b_256(data: &[u8]) -> [u8; HASH_BYTES] {
+    let mut hasher = Blake2bBuilder::new(HASH_BYTES)
+        .personal(CKB_HASH_PERSONALIZATION)
+        .build();
+    hasher.update(data);
+
+    let mut ret = [0u8; HASH_BYTES];
+    hasher.finalize(&mut ret);
+    ret
+}
+
+/// Build a DAS witness from its DataType and entity, it is the reverse of `parse_witness`.
+pub fn build_witness(data_type: DataType, entity: &[u8]) -> Vec<u8> {
+    let mut witness = Vec::with_capacity(WITNESS_HEADER_BYTES + WITNESS_TYPE_BYTES + entity.len());
+    witness.extend_from_slice(&WITNESS_HEADER);
+    witness.extend_from_slice(&(data_type as u32).to_le_bytes());
+    witness.extend_from_slice(entity);
+    witness
+}
+
+/// Split a DAS witness into its DataType and the entity following the header.
+pub fn parse_witness(witness: &[u8]) -> Option<(DataType, &[u8])> {
+    let header_bytes = WITNESS_HEADER_BYTES + WITNESS_TYPE_BYTES;
+    if witness.len() < header_bytes || witness[..WITNESS_HEADER_BYTES] != WITNESS_HEADER {
+        return None;
+    }
+
+    let mut buf = [0u8; WITNESS_TYPE_BYTES];
+    buf.copy_from_slice(&witness[WITNESS_HEADER_BYTES..header_bytes]);
+    DataType::try_from(u32::from_le_bytes(buf))
+        .ok()
+        .map(|data_type| (data_type, &witness[header_bytes..]))
+}
+
+/// Verify the header of a molecule table or dynvec and return the offsets of its items, the total size is appended as
+/// the end of the last item.
+// is_multiple_of is too new for the toolchain of contracts.
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+#[cfg(any(feature = "compatible", feature = "update", feature = "verify"))]
+pub(crate) fn verify_offsets(name: &str, slice: &[u8]) -> VerificationResult<Vec<usize>> {
+    let slice_len = slice.len();
+    if slice_len < NUMBER_SIZE {
+        return Err(VerificationError::HeaderIsBroken(
+            name.to_owned(),
+            NUMBER_SIZE,
+            slice_len,
+        ));
+    }
+    let total_size = molecule::unpack_number(slice) as usize;
+    if slice_len != total_size {
+        return Err(VerificationError::TotalSizeNotMatch(
+            name.to_owned(),
+            total_size,
+            slice_len,
+        ));
+    }
+    if slice_len == NUMBER_SIZE {
+        return Ok(vec![total_size]);
+    }
+    if slice_len < NUMBER_SIZE * 2 {
+        return Err(VerificationError::HeaderIsBroken(
+            name.to_owned(),
+            NUMBER_SIZE * 2,
+            slice_len,
+        ));
+    }
+
+    let offset_first = molecule::unpack_number(&slice[NUMBER_SIZE..]) as usize;
+    if offset_first % NUMBER_SIZE != 0 || offset_first < NUMBER_SIZE * 2 {
+        return Err(VerificationError::OffsetsNotMatch(name.to_owned()));
+    }
+    if slice_len < offset_first {
+        return Err(VerificationError::HeaderIsBroken(
+            name.to_owned(),
+            offset_first,
+            slice_len,
+        ));
+    }
+
+    let mut offsets: Vec<usize> = slice[NUMBER_SIZE..offset_first]
+        .chunks_exact(NUMBER_SIZE)
+        .map(|x| molecule::unpack_number(x) as usize)
+        .collect();
+    offsets.push(total_size);
+    if offsets.windows(2).any(|i| i[0] > i[1]) {
+        return Err(VerificationError::OffsetsNotMatch(name.to_owned()));
+    }
+
+    Ok(offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn build_and_parse_witness() {
+        let witness = build_witness(DataType::ConfigCellMain, &[1, 2, 3]);
+        assert_eq!(witness, vec![b'd', b'a', b's', 104, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(
+            parse_witness(&witness),
+            Some((DataType::ConfigCellMain, &[1u8, 2, 3][..]))
+        );
+
+        let witness = build_witness(DataType::ActionData, &[]);
+        assert_eq!(parse_witness(&witness), Some((DataType::ActionData, &[][..])));
+    }
+
+    #[test]
+    fn parse_invalid_witness() {
+        let witness = build_witness(DataType::ConfigCellMain, &[1, 2, 3]);
+        assert_eq!(parse_witness(&witness[..6]), None);
+        assert_eq!(parse_witness(&[]), None);
+
+        let mut wrong_header = witness.clone();
+        wrong_header[0] = b'x';
+        assert_eq!(parse_witness(&wrong_header), None);
+
+        let mut unknown_type = witness;
+        unknown_type[3..7].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(parse_witness(&unknown_type), None);
+    }
 }
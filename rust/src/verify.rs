@@ -0,0 +1,579 @@
+use super::{error::DasTypesError, schemas::packed::*, util::verify_offsets};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use molecule::prelude::*;
+
+/// Verify molecule data and report which field is broken.
+///
+/// Molecule only reports the reader and the kind of error, this layer walks into the tables, vectors and options to
+/// find the innermost field which fails, then reports its path like `AccountCellData.records[3].record_key` and the
+/// byte offset of the field in the slice.
+pub trait VerifyPath {
+    fn verify_path(slice: &[u8], compatible: bool) -> Result<(), DasTypesError>
+    where
+        Self: Entity,
+    {
+        let mut path = String::from(Self::NAME);
+        Self::verify_at(slice, compatible, &mut path, 0)
+    }
+
+    /// Verify the slice which is at the path and offset of the outermost data.
+    fn verify_at(slice: &[u8], compatible: bool, path: &mut String, offset: usize) -> Result<(), DasTypesError>;
+}
+
+type VerifyAtFn = fn(&[u8], bool, &mut String, usize) -> Result<(), DasTypesError>;
+
+/// Split a table or dynvec into the ranges of its items, it returns None if the header is broken.
+fn split_offsets(name: &str, slice: &[u8]) -> Option<Vec<(usize, usize)>> {
+    verify_offsets(name, slice)
+        .ok()
+        .map(|offsets| offsets.windows(2).map(|i| (i[0], i[1])).collect())
+}
+
+// A segment appended to the path when walking into a table or vector.
+enum Segment {
+    Field(&'static str),
+    Index(usize),
+}
+
+/// Verify every item, the path is extended by the segment of the item while it is verified.
+fn verify_items(
+    slice: &[u8],
+    compatible: bool,
+    path: &mut String,
+    offset: usize,
+    items: impl Iterator<Item = (Segment, VerifyAtFn, (usize, usize))>,
+) -> Result<(), DasTypesError> {
+    for (segment, verify_at, (start, end)) in items {
+        let path_len = path.len();
+        match segment {
+            Segment::Field(name) => {
+                path.push('.');
+                path.push_str(name);
+            }
+            Segment::Index(index) => {
+                let _ = write!(path, "[{}]", index);
+            }
+        }
+        verify_at(&slice[start..end], compatible, path, offset + start)?;
+        path.truncate(path_len);
+    }
+
+    Ok(())
+}
+
+/// Types without inner tables, molecule already reports everything of them.
+macro_rules! impl_verify_path_leaf {
+    ($( $entity:ident => $reader:ident ),+) => {
+        $(
+            impl VerifyPath for $entity {
+                fn verify_at(
+                    slice: &[u8],
+                    compatible: bool,
+                    path: &mut String,
+                    offset: usize,
+                ) -> Result<(), DasTypesError> {
+                    $reader::verify(slice, compatible)
+                        .map_err(|err| DasTypesError::VerificationAt(path.clone(), offset, err))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_verify_path_option {
+    ($( $entity:ident => $inner:ident ),+) => {
+        $(
+            impl VerifyPath for $entity {
+                fn verify_at(
+                    slice: &[u8],
+                    compatible: bool,
+                    path: &mut String,
+                    offset: usize,
+                ) -> Result<(), DasTypesError> {
+                    if slice.is_empty() {
+                        Ok(())
+                    } else {
+                        $inner::verify_at(slice, compatible, path, offset)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_verify_path_dynvec {
+    ($( $entity:ident => $reader:ident, $item:ident ),+) => {
+        $(
+            impl VerifyPath for $entity {
+                fn verify_at(
+                    slice: &[u8],
+                    compatible: bool,
+                    path: &mut String,
+                    offset: usize,
+                ) -> Result<(), DasTypesError> {
+                    let err = match $reader::verify(slice, compatible) {
+                        Ok(()) => return Ok(()),
+                        Err(err) => err,
+                    };
+
+                    if let Some(items) = split_offsets($reader::NAME, slice) {
+                        verify_items(
+                            slice,
+                            compatible,
+                            path,
+                            offset,
+                            items
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, range)| (Segment::Index(index), $item::verify_at as VerifyAtFn, range)),
+                        )?;
+                    }
+
+                    Err(DasTypesError::VerificationAt(path.clone(), offset, err))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_verify_path_table {
+    ($( $entity:ident => $reader:ident { $( $field:ident: $field_type:ident ),+ } );+) => {
+        $(
+            impl VerifyPath for $entity {
+                fn verify_at(
+                    slice: &[u8],
+                    compatible: bool,
+                    path: &mut String,
+                    offset: usize,
+                ) -> Result<(), DasTypesError> {
+                    let err = match $reader::verify(slice, compatible) {
+                        Ok(()) => return Ok(()),
+                        Err(err) => err,
+                    };
+
+                    match split_offsets($reader::NAME, slice) {
+                        // Tables with missing fields are reported as a whole.
+                        Some(fields) if fields.len() >= $reader::FIELD_COUNT => {
+                            let verifiers: [(&'static str, VerifyAtFn); $reader::FIELD_COUNT] =
+                                [$( (stringify!($field), $field_type::verify_at) ),+];
+                            verify_items(
+                                slice,
+                                compatible,
+                                path,
+                                offset,
+                                verifiers
+                                    .iter()
+                                    .zip(fields)
+                                    .map(|((name, verify_at), range)| (Segment::Field(name), *verify_at, range)),
+                            )?;
+                        }
+                        _ => {}
+                    }
+
+                    Err(DasTypesError::VerificationAt(path.clone(), offset, err))
+                }
+            }
+        )+
+    };
+}
+
+impl_verify_path_leaf!(
+    Byte => ByteReader,
+    Uint8 => Uint8Reader,
+    Uint32 => Uint32Reader,
+    Uint64 => Uint64Reader,
+    Bytes => BytesReader,
+    Timestamp => TimestampReader,
+    Hash => HashReader,
+    OutPoint => OutPointReader,
+    AccountId => AccountIdReader
+);
+
+impl_verify_path_option!(
+    HashOpt => Hash,
+    ScriptOpt => Script,
+    DataEntityOpt => DataEntity
+);
+
+impl_verify_path_dynvec!(
+    Chars => CharsReader, Bytes,
+    PriceConfigList => PriceConfigListReader, PriceConfig,
+    SliceList => SliceListReader, SL,
+    SL => SLReader, ProposalItem,
+    IncomeRecords => IncomeRecordsReader, IncomeRecord,
+    Records => RecordsReader, Record,
+    AccountChars => AccountCharsReader, AccountChar
+);
+
+impl_verify_path_table!(
+    Script => ScriptReader {
+        code_hash: Hash,
+        hash_type: Byte,
+        args: Bytes
+    };
+    Data => DataReader {
+        dep: DataEntityOpt,
+        old: DataEntityOpt,
+        new: DataEntityOpt
+    };
+    DataEntity => DataEntityReader {
+        index: Uint32,
+        version: Uint32,
+        entity: Bytes
+    };
+    ActionData => ActionDataReader {
+        action: Bytes,
+        params: Bytes
+    };
+    ConfigCellMain => ConfigCellMainReader {
+        status: Uint8,
+        type_id_table: TypeIdTable,
+        das_lock_out_point_table: DasLockOutPointTable
+    };
+    TypeIdTable => TypeIdTableReader {
+        account_cell: Hash,
+        apply_register_cell: Hash,
+        balance_cell: Hash,
+        income_cell: Hash,
+        pre_account_cell: Hash,
+        proposal_cell: Hash,
+        account_sale_cell: Hash,
+        account_auction_cell: Hash,
+        offer_cell: Hash,
+        reverse_record_cell: Hash,
+        sub_account_cell: Hash
+    };
+    DasLockOutPointTable => DasLockOutPointTableReader {
+        ckb_signall: OutPoint,
+        ckb_multisign: OutPoint,
+        ckb_anyone_can_pay: OutPoint,
+        eth: OutPoint,
+        tron: OutPoint,
+        ed25519: OutPoint
+    };
+    ConfigCellAccount => ConfigCellAccountReader {
+        max_length: Uint32,
+        basic_capacity: Uint64,
+        prepared_fee_capacity: Uint64,
+        expiration_grace_period: Uint32,
+        record_min_ttl: Uint32,
+        record_size_limit: Uint32,
+        transfer_account_fee: Uint64,
+        edit_manager_fee: Uint64,
+        edit_records_fee: Uint64,
+        common_fee: Uint64,
+        transfer_account_throttle: Uint32,
+        edit_manager_throttle: Uint32,
+        edit_records_throttle: Uint32,
+        common_throttle: Uint32
+    };
+    ConfigCellApply => ConfigCellApplyReader {
+        apply_min_waiting_block_number: Uint32,
+        apply_max_waiting_block_number: Uint32
+    };
+    ConfigCellPrice => ConfigCellPriceReader {
+        discount: DiscountConfig,
+        prices: PriceConfigList
+    };
+    DiscountConfig => DiscountConfigReader {
+        invited_discount: Uint32
+    };
+    PriceConfig => PriceConfigReader {
+        length: Uint8,
+        new: Uint64,
+        renew: Uint64
+    };
+    ConfigCellProposal => ConfigCellProposalReader {
+        proposal_min_confirm_interval: Uint8,
+        proposal_min_extend_interval: Uint8,
+        proposal_min_recycle_interval: Uint8,
+        proposal_max_account_affect: Uint32,
+        proposal_max_pre_account_contain: Uint32
+    };
+    ConfigCellProfitRate => ConfigCellProfitRateReader {
+        inviter: Uint32,
+        channel: Uint32,
+        proposal_create: Uint32,
+        proposal_confirm: Uint32,
+        income_consolidate: Uint32,
+        sale_buyer_inviter: Uint32,
+        sale_buyer_channel: Uint32,
+        sale_das: Uint32,
+        auction_bidder_inviter: Uint32,
+        auction_bidder_channel: Uint32,
+        auction_das: Uint32,
+        auction_prev_bidder: Uint32
+    };
+    ConfigCellIncome => ConfigCellIncomeReader {
+        basic_capacity: Uint64,
+        max_records: Uint32,
+        min_transfer_capacity: Uint64
+    };
+    ConfigCellRelease => ConfigCellReleaseReader {
+        lucky_number: Uint32
+    };
+    ConfigCellSecondaryMarket => ConfigCellSecondaryMarketReader {
+        common_fee: Uint64,
+        sale_min_price: Uint64,
+        sale_expiration_limit: Uint32,
+        sale_description_bytes_limit: Uint32,
+        sale_cell_basic_capacity: Uint64,
+        sale_cell_prepared_fee_capacity: Uint64,
+        auction_max_extendable_duration: Uint32,
+        auction_duration_increment_each_bid: Uint32,
+        auction_min_opening_price: Uint64,
+        auction_min_increment_rate_each_bid: Uint32,
+        auction_description_bytes_limit: Uint32,
+        auction_cell_basic_capacity: Uint64,
+        auction_cell_prepared_fee_capacity: Uint64,
+        offer_min_price: Uint64,
+        offer_cell_basic_capacity: Uint64,
+        offer_cell_prepared_fee_capacity: Uint64,
+        offer_message_bytes_limit: Uint32
+    };
+    ConfigCellReverseResolution => ConfigCellReverseResolutionReader {
+        record_basic_capacity: Uint64,
+        record_prepared_fee_capacity: Uint64,
+        common_fee: Uint64
+    };
+    ConfigCellSubAccount => ConfigCellSubAccountReader {
+        basic_capacity: Uint64,
+        prepared_fee_capacity: Uint64,
+        new_sub_account_price: Uint64,
+        renew_sub_account_price: Uint64,
+        common_fee: Uint64,
+        create_fee: Uint64,
+        edit_fee: Uint64,
+        renew_fee: Uint64,
+        recycle_fee: Uint64
+    };
+    ProposalCellData => ProposalCellDataReader {
+        proposer_lock: Script,
+        created_at_height: Uint64,
+        slices: SliceList
+    };
+    ProposalItem => ProposalItemReader {
+        account_id: AccountId,
+        item_type: Uint8,
+        next: AccountId
+    };
+    IncomeCellData => IncomeCellDataReader {
+        creator: Script,
+        records: IncomeRecords
+    };
+    IncomeRecord => IncomeRecordReader {
+        belong_to: Script,
+        capacity: Uint64
+    };
+    AccountCellDataV1 => AccountCellDataV1Reader {
+        id: AccountId,
+        account: AccountChars,
+        registered_at: Uint64,
+        updated_at: Uint64,
+        status: Uint8,
+        records: Records
+    };
+    AccountCellDataV2 => AccountCellDataV2Reader {
+        id: AccountId,
+        account: AccountChars,
+        registered_at: Uint64,
+        last_transfer_account_at: Uint64,
+        last_edit_manager_at: Uint64,
+        last_edit_records_at: Uint64,
+        status: Uint8,
+        records: Records
+    };
+    AccountCellData => AccountCellDataReader {
+        id: AccountId,
+        account: AccountChars,
+        registered_at: Uint64,
+        last_transfer_account_at: Uint64,
+        last_edit_manager_at: Uint64,
+        last_edit_records_at: Uint64,
+        status: Uint8,
+        records: Records,
+        enable_sub_account: Uint8,
+        renew_sub_account_price: Uint64
+    };
+    Record => RecordReader {
+        record_type: Bytes,
+        record_key: Bytes,
+        record_label: Bytes,
+        record_value: Bytes,
+        record_ttl: Uint32
+    };
+    AccountSaleCellDataV1 => AccountSaleCellDataV1Reader {
+        account_id: AccountId,
+        account: Bytes,
+        price: Uint64,
+        description: Bytes,
+        started_at: Uint64
+    };
+    AccountSaleCellData => AccountSaleCellDataReader {
+        account_id: AccountId,
+        account: Bytes,
+        price: Uint64,
+        description: Bytes,
+        started_at: Uint64,
+        buyer_inviter_profit_rate: Uint32
+    };
+    AccountAuctionCellData => AccountAuctionCellDataReader {
+        account_id: AccountId,
+        account: Bytes,
+        description: Bytes,
+        opening_price: Uint64,
+        increment_rate_each_bid: Uint32,
+        started_at: Uint64,
+        ended_at: Uint64,
+        current_bidder_lock: Script,
+        current_bid_price: Uint64,
+        prev_bidder_profit_rate: Uint32
+    };
+    PreAccountCellData => PreAccountCellDataReader {
+        account: AccountChars,
+        refund_lock: Script,
+        owner_lock_args: Bytes,
+        inviter_id: Bytes,
+        inviter_lock: ScriptOpt,
+        channel_lock: ScriptOpt,
+        price: PriceConfig,
+        quote: Uint64,
+        invited_discount: Uint32,
+        created_at: Uint64
+    };
+    AccountChar => AccountCharReader {
+        char_set_name: Uint32,
+        bytes: Bytes
+    };
+    OfferCellData => OfferCellDataReader {
+        account: Bytes,
+        price: Uint64,
+        message: Bytes,
+        inviter_lock: Script,
+        channel_lock: Script
+    };
+    SubAccount => SubAccountReader {
+        lock: Script,
+        id: AccountId,
+        account: AccountChars,
+        suffix: Bytes,
+        registered_at: Uint64,
+        expired_at: Uint64,
+        status: Uint8,
+        records: Records,
+        nonce: Uint64,
+        enable_sub_account: Uint8,
+        renew_sub_account_price: Uint64
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::append_field;
+    use alloc::vec;
+
+    fn offset_in(outer: &[u8], inner: &[u8]) -> usize {
+        inner.as_ptr() as usize - outer.as_ptr() as usize
+    }
+
+    fn expect_error_at(result: Result<(), DasTypesError>, expected_path: &str, expected_offset: usize) {
+        match result {
+            Err(DasTypesError::VerificationAt(path, offset, _)) => {
+                assert_eq!(path, expected_path);
+                assert_eq!(offset, expected_offset);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    fn account_cell_data() -> AccountCellData {
+        let record = Record::new_builder().record_key(Bytes::from(&b"abc"[..])).build();
+        let records = Records::new_builder()
+            .extend(vec![Record::default(), record.clone(), record])
+            .build();
+        AccountCellData::new_builder().records(records).build()
+    }
+
+    #[test]
+    fn valid_data_passes() {
+        let data = account_cell_data();
+        assert!(AccountCellData::verify_path(data.as_slice(), false).is_ok());
+        assert!(AccountCellData::verify_path(data.as_slice(), true).is_ok());
+    }
+
+    #[test]
+    fn broken_field_in_vector_is_reported_with_path_and_offset() {
+        let data = account_cell_data();
+        let key = data.as_reader().records().get(2).unwrap().record_key();
+        let key_offset = offset_in(data.as_slice(), key.as_slice());
+
+        let mut bytes = data.as_slice().to_vec();
+        // The length header of the bytes no longer matches its size.
+        bytes[key_offset] = 9;
+        expect_error_at(
+            AccountCellData::verify_path(&bytes, false),
+            "AccountCellData.records[2].record_key",
+            key_offset,
+        );
+    }
+
+    #[test]
+    fn broken_field_in_option_is_reported_with_path_and_offset() {
+        let lock = Script::new_builder().args(Bytes::from(&b"args"[..])).build();
+        let data = PreAccountCellData::new_builder()
+            .inviter_lock(ScriptOpt::new_builder().set(Some(lock)).build())
+            .build();
+        let args = data.as_reader().inviter_lock().to_opt().unwrap().args();
+        let args_offset = offset_in(data.as_slice(), args.as_slice());
+
+        let mut bytes = data.as_slice().to_vec();
+        bytes[args_offset] = 0;
+        expect_error_at(
+            PreAccountCellData::verify_path(&bytes, false),
+            "PreAccountCellData.inviter_lock.args",
+            args_offset,
+        );
+    }
+
+    #[test]
+    fn broken_header_is_reported_at_the_table() {
+        let data = account_cell_data();
+        let bytes = data.as_slice();
+        expect_error_at(AccountCellData::verify_path(&bytes[..10], false), "AccountCellData", 0);
+        expect_error_at(AccountCellData::verify_path(&bytes[..2], false), "AccountCellData", 0);
+
+        // The first offset points into the header.
+        let mut bytes = bytes.to_vec();
+        bytes[4] = 8;
+        expect_error_at(AccountCellData::verify_path(&bytes, false), "AccountCellData", 0);
+    }
+
+    #[test]
+    fn appended_fields_are_only_accepted_in_compatible_mode() {
+        let data = account_cell_data();
+        let bytes = append_field(data.as_slice(), &[1, 2, 3]);
+        expect_error_at(AccountCellData::verify_path(&bytes, false), "AccountCellData", 0);
+        assert!(AccountCellData::verify_path(&bytes, true).is_ok());
+
+        // Tables with missing fields are reported as a whole, even in compatible mode.
+        let v2 = AccountCellDataV2::default();
+        expect_error_at(AccountCellData::verify_path(v2.as_slice(), true), "AccountCellData", 0);
+    }
+
+    #[test]
+    fn split_offsets_of_tables() {
+        let record = Record::default();
+        let ranges = split_offsets(RecordReader::NAME, record.as_slice()).unwrap();
+        assert_eq!(ranges.len(), RecordReader::FIELD_COUNT);
+        assert_eq!(ranges.first().unwrap().0, 4 * (RecordReader::FIELD_COUNT + 1));
+        assert_eq!(ranges.last().unwrap().1, record.as_slice().len());
+
+        assert_eq!(
+            split_offsets(RecordsReader::NAME, Records::default().as_slice()),
+            Some(vec![])
+        );
+        assert_eq!(split_offsets(RecordReader::NAME, &record.as_slice()[..6]), None);
+    }
+}
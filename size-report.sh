@@ -0,0 +1,40 @@
+#!/bin/bash
+
+# Report how much each cargo feature of das-types adds on top of the minimal reader-only build.
+#
+# Usage: ./size-report.sh [target], e.g. ./size-report.sh riscv64imac-unknown-none-elf
+#
+# The size is measured on the release rlib, which is only a rough proxy of what ends up in a contract
+# binary, but it is good enough to compare the features with each other.
+
+DIST_RUST_PATH="${PWD}/rust"
+TARGET_DIR="${PWD}/target/size-report"
+BASE_FEATURES="ckb-std"
+FEATURES="compatible hash cell-data config type-id diff update calculator owned verify prettier serde json"
+
+target_args=""
+target_path="${TARGET_DIR}/release"
+if [[ -n $1 ]]; then
+    target_args="--target $1"
+    target_path="${TARGET_DIR}/$1/release"
+fi
+
+function rlib_size() {
+    local features=$1
+
+    rm -f ${target_path}/libdas_types.rlib
+    (cd $DIST_RUST_PATH && cargo build --release --quiet --target-dir $TARGET_DIR $target_args \
+        --no-default-features --features "$features") || exit 1
+    stat -c %s ${target_path}/libdas_types.rlib
+}
+
+base=$(rlib_size "$BASE_FEATURES")
+printf "%-12s %12s %12s\n" "feature" "bytes" "added"
+printf "%-12s %12d %12s\n" "(default)" $base "-"
+
+for feature in $FEATURES; do
+    size=$(rlib_size "$BASE_FEATURES $feature")
+    printf "%-12s %12d %+12d\n" $feature $size $((size - base))
+done
+
+echo "Done ✔"